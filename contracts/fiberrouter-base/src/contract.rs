@@ -8,7 +8,7 @@ use cw_storage_plus::Bound;
 use fiberrouter::{
     FiberRouterExecuteMsg, FiberRouterQueryMsg, MigrateMsg, SetPoolEvent, TransferOwnershipEvent,
 };
-use multiswap::{MultiswapContract, MultiswapExecuteMsg};
+use multiswap::{AssetInfo, MultiswapContract, MultiswapExecuteMsg, WithdrawSignedItem};
 
 use crate::error::{self, ContractError};
 use crate::msg::InstantiateMsg;
@@ -55,20 +55,37 @@ pub fn execute(
         FiberRouterExecuteMsg::SetPool { pool } => execute_set_pool(env, pool),
         FiberRouterExecuteMsg::WithdrawSigned {
             payee,
-            token,
+            asset_info,
             amount,
             salt,
-            signature,
-        } => execute_withdraw_signed(env, payee, token, amount, salt, signature),
+            signatures,
+            expires_at,
+            msg,
+        } => execute_withdraw_signed(
+            env,
+            WithdrawSignedItem {
+                payee,
+                asset_info,
+                amount,
+                salt,
+                signatures,
+                expires_at,
+                msg,
+            },
+        ),
+        FiberRouterExecuteMsg::WithdrawSignedBatch {
+            items,
+            skip_processed,
+        } => execute_withdraw_signed_batch(env, items, skip_processed),
         FiberRouterExecuteMsg::Swap {
-            token,
+            asset_info,
             amount,
             target_chain_id,
             target_token,
             target_address,
         } => execute_swap(
             env,
-            token,
+            asset_info,
             amount,
             target_chain_id,
             target_token,
@@ -120,25 +137,32 @@ pub fn execute_set_pool(env: ExecuteEnv, new_pool: String) -> Result<Response, C
 
 pub fn execute_withdraw_signed(
     env: ExecuteEnv,
-    payee: String,
-    token: String,
-    amount: Uint128,
-    salt: String,
-    signature: String,
+    item: WithdrawSignedItem,
 ) -> Result<Response, ContractError> {
     let deps = env.deps;
     let pool = POOL.load(deps.storage)?;
     let contract_addr = deps.api.addr_validate(pool.as_str())?;
     // MultiswapContract is a function helper that provides several queries and message builder.
     let multiswap = MultiswapContract(contract_addr);
+    let WithdrawSignedItem {
+        payee,
+        asset_info,
+        amount,
+        salt,
+        signatures,
+        expires_at,
+        msg,
+    } = item;
     // Call multiswap withdraw signed
     let msg = multiswap.call(
         MultiswapExecuteMsg::WithdrawSigned {
             payee: payee.to_string(),
-            token: token.to_string(),
+            asset_info: asset_info.clone(),
             amount: amount.clone(),
             salt: salt.to_string(),
-            signature: signature.to_string(),
+            signatures,
+            expires_at,
+            msg,
         },
         vec![],
     )?;
@@ -147,14 +171,41 @@ pub fn execute_withdraw_signed(
         .add_message(msg)
         .add_attribute("action", "withdraw_signed")
         .add_attribute("payee", payee.to_string())
-        .add_attribute("token", token.to_string())
+        .add_attribute("token", asset_info.token())
         .add_attribute("amount", amount.to_string());
     Ok(res)
 }
 
+pub fn execute_withdraw_signed_batch(
+    env: ExecuteEnv,
+    items: Vec<WithdrawSignedItem>,
+    skip_processed: bool,
+) -> Result<Response, ContractError> {
+    let deps = env.deps;
+    let pool = POOL.load(deps.storage)?;
+    let contract_addr = deps.api.addr_validate(pool.as_str())?;
+    // MultiswapContract is a function helper that provides several queries and message builder.
+    let multiswap = MultiswapContract(contract_addr);
+    let count = items.len();
+    // Call multiswap withdraw signed batch
+    let msg = multiswap.call(
+        MultiswapExecuteMsg::WithdrawSignedBatch {
+            items,
+            skip_processed,
+        },
+        vec![],
+    )?;
+
+    let res = Response::new()
+        .add_message(msg)
+        .add_attribute("action", "withdraw_signed_batch")
+        .add_attribute("count", count.to_string());
+    Ok(res)
+}
+
 pub fn execute_swap(
     env: ExecuteEnv,
-    token: String,
+    asset_info: AssetInfo,
     amount: Uint128,
     target_chain_id: String,
     target_token: String,
@@ -168,7 +219,7 @@ pub fn execute_swap(
     // Call multiswap swap
     let msg = multiswap.call(
         MultiswapExecuteMsg::Swap {
-            token: token.to_string(),
+            asset_info: asset_info.clone(),
             amount: amount.clone(),
             target_chain_id: target_chain_id.to_string(),
             target_token: target_token.to_string(),
@@ -180,7 +231,7 @@ pub fn execute_swap(
     let res = Response::new()
         .add_message(msg)
         .add_attribute("action", "swap")
-        .add_attribute("token", token.to_string())
+        .add_attribute("token", asset_info.token())
         .add_attribute("amount", amount.to_string())
         .add_attribute("target_chain_id", target_chain_id.to_string())
         .add_attribute("target_token", target_token.to_string())
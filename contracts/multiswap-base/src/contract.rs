@@ -1,35 +1,79 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, Addr, Api, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Order, Response, StdError, StdResult, Storage, Uint128,
+    attr, coins, from_binary, to_binary, to_vec, Addr, Api, BankMsg, Binary, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use cw20::{
+    BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse,
+    TokenInfoResponse,
 };
 use cw_storage_plus::Bound;
 
 use multiswap::{
-    AddFoundryAssetEvent, AddLiquidityEvent, AddSignerEvent, BridgeSwapEvent,
-    BridgeWithdrawSignedEvent, Liquidity, MigrateMsg, MultiswapExecuteMsg, MultiswapQueryMsg,
-    RemoveFoundryAssetEvent, RemoveLiquidityEvent, RemoveSignerEvent, TransferOwnershipEvent,
+    eip712_domain_separator, eip712_withdraw_hash, eth_message_hash, keccak256,
+    AddFoundryAssetEvent, AddLiquidityEvent, AddLiquidityProviderEvent, AddSignerEvent, Approval,
+    ApproveOperatorEvent, AssetInfo, AssetStatus, BridgeSwapEvent, BridgeWithdrawSignedEvent,
+    Claim, ClaimLiquidityEvent, ClaimRewardsEvent, Config, Cw20HookMsg, DelistFoundryAssetEvent,
+    FeeDistribution, FoundryAsset, KeyType, Liquidity, MigrateMsg, MultiswapExecuteMsg,
+    MultiswapQueryMsg, PoolInfo, PoolTotal, RegisterLpTokenEvent, RemoveFoundryAssetEvent,
+    RemoveLiquidityEvent, RemoveLiquidityProviderEvent, RemoveSignerEvent, RevokeOperatorEvent,
+    SetProviderAllowlistEvent, SetSwapFeeEvent, SetThresholdEvent, SetUnbondingPeriodEvent,
+    SignMode, SignatureVerification, Signer, SignerInfo, SignerStatus, TransferOwnershipEvent,
+    UpdateAssetLimitsEvent, VerifyWithdrawResponse, WithdrawCallFallbackEvent, WithdrawSignMessage,
+    WithdrawSignature, WithdrawSignedItem, WrapLiquidityEvent,
 };
 
 use crate::error::{self, ContractError};
-use crate::msg::InstantiateMsg;
-use crate::state::{FOUNDRY_ASSETS, LIQUIDITIES, OWNER, SIGNERS};
-use cw_utils::Event;
+use crate::msg::{Cw20InstantiateMsg, InstantiateMsg};
+use crate::state::{
+    LiquidityPool, LiquidityPosition, WithdrawCall, AVAILABLE_LIQUIDITIES, CLAIMS, CONFIG,
+    FOUNDRY_ASSETS, LEGACY_FOUNDRY_ASSETS, LEGACY_LIQUIDITIES, LEGACY_SIGNERS, LIQUIDITIES,
+    LIQUIDITY_PROVIDERS, LP_TOKENS, LP_TOKEN_ASSETS, LP_TOKEN_INSTANTIATES, OPERATORS, OWNER,
+    POOLS, REPLY_SEQ, SIGNERS, UNBONDING_PERIODS, USED_MESSAGES, WITHDRAW_CALLS,
+};
+use cw_utils::{parse_reply_instantiate_data, Duration, Event, Expiration};
 // use crate::state::{APPROVES, BALANCES, MINTER, TOKENS};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:multiswap-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_THRESHOLD: u32 = 1;
+const MAX_SWAP_FEE_BPS: u16 = 10_000;
+const LP_TOKEN_SYMBOL: &str = "MSLP";
+const LP_TOKEN_DECIMALS: u8 = 6;
+/// Shares of the first deposit into a pool that are locked forever
+const MINIMUM_LIQUIDITY: Uint128 = Uint128::new(1_000);
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let owner = deps.api.addr_validate(&msg.owner)?;
     OWNER.save(deps.storage, &owner)?;
+    let threshold = msg.threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if threshold == 0 {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    let swap_fee_bps = msg.swap_fee_bps.unwrap_or_default();
+    if swap_fee_bps > MAX_SWAP_FEE_BPS {
+        return Err(ContractError::InvalidSwapFee {});
+    }
+    let config = Config {
+        threshold,
+        sign_mode: msg.sign_mode.unwrap_or_default(),
+        swap_fee_bps,
+        fee_distribution: msg.fee_distribution.unwrap_or_default(),
+        lp_token_code_id: msg.lp_token_code_id,
+        provider_allowlist: false,
+    };
+    CONFIG.save(deps.storage, &config)?;
     Ok(Response::default())
 }
 
@@ -52,34 +96,101 @@ pub fn execute(
         MultiswapExecuteMsg::TransferOwnership { new_owner } => {
             execute_ownership_transfer(env, new_owner)
         }
-        MultiswapExecuteMsg::AddSigner { signer } => execute_add_signer(env, signer),
-        MultiswapExecuteMsg::RemoveSigner { signer } => execute_remove_signer(env, signer),
-        MultiswapExecuteMsg::AddFoundryAsset { token } => execute_add_foundry_asset(env, token),
+        MultiswapExecuteMsg::AddSigner {
+            signer,
+            key_type,
+            active_from,
+        } => execute_add_signer(env, signer, key_type.unwrap_or_default(), active_from),
+        MultiswapExecuteMsg::RemoveSigner {
+            signer,
+            valid_until,
+        } => execute_remove_signer(env, signer, valid_until),
+        MultiswapExecuteMsg::SetThreshold { threshold } => execute_set_threshold(env, threshold),
+        MultiswapExecuteMsg::SetSwapFee {
+            swap_fee_bps,
+            fee_distribution,
+        } => execute_set_swap_fee(env, swap_fee_bps, fee_distribution),
+        MultiswapExecuteMsg::AddFoundryAsset {
+            token,
+            asset_info,
+            symbol,
+            decimals,
+        } => execute_add_foundry_asset(env, token, asset_info, symbol, decimals),
         MultiswapExecuteMsg::RemoveFoundryAsset { token } => {
             execute_remove_foundry_asset(env, token)
         }
-        MultiswapExecuteMsg::AddLiquidity { token, amount } => {
-            execute_add_liquidity(env, token, amount)
+        MultiswapExecuteMsg::UpdateAssetLimits {
+            token,
+            max_total_liquidity,
+            max_per_provider,
+        } => execute_update_asset_limits(env, token, max_total_liquidity, max_per_provider),
+        MultiswapExecuteMsg::AddLiquidityProvider { provider } => {
+            execute_add_liquidity_provider(env, provider)
+        }
+        MultiswapExecuteMsg::RemoveLiquidityProvider { provider } => {
+            execute_remove_liquidity_provider(env, provider)
+        }
+        MultiswapExecuteMsg::SetProviderAllowlist { token, enabled } => {
+            execute_set_provider_allowlist(env, token, enabled)
         }
-        MultiswapExecuteMsg::RemoveLiquidity { token, amount } => {
-            execute_remove_liquidity(env, token, amount)
+        MultiswapExecuteMsg::AddLiquidity {
+            asset_info,
+            amount,
+            owner,
+        } => execute_add_liquidity(env, asset_info, amount, owner),
+        MultiswapExecuteMsg::RemoveLiquidity {
+            token,
+            shares,
+            owner,
+        } => execute_remove_liquidity(env, token, shares, owner),
+        MultiswapExecuteMsg::ApproveOperator { operator, expires } => {
+            execute_approve_operator(env, operator, expires)
+        }
+        MultiswapExecuteMsg::RevokeOperator { operator } => execute_revoke_operator(env, operator),
+        MultiswapExecuteMsg::SetUnbondingPeriod {
+            token,
+            unbonding_period,
+        } => execute_set_unbonding_period(env, token, unbonding_period),
+        MultiswapExecuteMsg::ClaimLiquidity {} => execute_claim_liquidity(env),
+        MultiswapExecuteMsg::ClaimRewards { token } => execute_claim_rewards(env, token),
+        MultiswapExecuteMsg::CreateLpToken { token } => execute_create_lp_token(env, token),
+        MultiswapExecuteMsg::WrapLiquidity { token, shares } => {
+            execute_wrap_liquidity(env, token, shares)
         }
+        MultiswapExecuteMsg::Receive(msg) => execute_receive(env, msg),
         MultiswapExecuteMsg::WithdrawSigned {
             payee,
-            token,
+            asset_info,
             amount,
             salt,
-            signature,
-        } => execute_withdraw_signed(env, payee, token, amount, salt, signature),
+            signatures,
+            expires_at,
+            msg,
+        } => execute_withdraw_signed(
+            env,
+            WithdrawSignedItem {
+                payee,
+                asset_info,
+                amount,
+                salt,
+                signatures,
+                expires_at,
+                msg,
+            },
+        ),
+        MultiswapExecuteMsg::WithdrawSignedBatch {
+            items,
+            skip_processed,
+        } => execute_withdraw_signed_batch(env, items, skip_processed),
         MultiswapExecuteMsg::Swap {
-            token,
+            asset_info,
             amount,
             target_chain_id,
             target_token,
             target_address,
         } => execute_swap(
             env,
-            token,
+            asset_info,
             amount,
             target_chain_id,
             target_token,
@@ -110,7 +221,12 @@ pub fn execute_ownership_transfer(
     Ok(rsp)
 }
 
-pub fn execute_add_signer(env: ExecuteEnv, signer: String) -> Result<Response, ContractError> {
+pub fn execute_add_signer(
+    env: ExecuteEnv,
+    signer: String,
+    key_type: KeyType,
+    active_from: Option<u64>,
+) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
         env,
@@ -121,18 +237,34 @@ pub fn execute_add_signer(env: ExecuteEnv, signer: String) -> Result<Response, C
         return Err(ContractError::Unauthorized {});
     }
 
+    let signer = normalize_signer_key(&signer, &key_type)?;
     let mut rsp = Response::default();
-    SIGNERS.save(deps.storage, signer.as_str(), &signer.to_string())?;
+    SIGNERS.save(
+        deps.storage,
+        signer.as_str(),
+        &Signer {
+            signer: signer.to_string(),
+            key_type: key_type.clone(),
+            active_from,
+            valid_until: None,
+        },
+    )?;
 
     let event = AddSignerEvent {
         from: info.sender.as_str(),
         signer: signer.as_str(),
+        key_type: key_type_name(&key_type),
+        active_from,
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_remove_signer(env: ExecuteEnv, signer: String) -> Result<Response, ContractError> {
+pub fn execute_remove_signer(
+    env: ExecuteEnv,
+    signer: String,
+    valid_until: Option<u64>,
+) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
         env,
@@ -143,21 +275,36 @@ pub fn execute_remove_signer(env: ExecuteEnv, signer: String) -> Result<Response
         return Err(ContractError::Unauthorized {});
     }
 
+    let signer = signer_key(&signer);
     let mut rsp = Response::default();
-    SIGNERS.remove(deps.storage, signer.as_str());
+    match valid_until {
+        // keep accepting the signer until the rotation grace window ends
+        Some(valid_until) => {
+            SIGNERS.update(
+                deps.storage,
+                signer.as_str(),
+                |registered| match registered {
+                    Some(mut registered) => {
+                        registered.valid_until = Some(valid_until);
+                        Ok(registered)
+                    }
+                    None => Err(ContractError::InvalidSigner {}),
+                },
+            )?;
+        }
+        None => SIGNERS.remove(deps.storage, signer.as_str()),
+    }
 
     let event = RemoveSignerEvent {
         from: info.sender.as_str(),
         signer: signer.as_str(),
+        valid_until,
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_add_foundry_asset(
-    env: ExecuteEnv,
-    token: String,
-) -> Result<Response, ContractError> {
+pub fn execute_set_threshold(env: ExecuteEnv, threshold: u32) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
         env,
@@ -167,21 +314,28 @@ pub fn execute_add_foundry_asset(
     if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
+    if threshold == 0 {
+        return Err(ContractError::InvalidThreshold {});
+    }
 
     let mut rsp = Response::default();
-    FOUNDRY_ASSETS.save(deps.storage, token.as_str(), &token.to_string())?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.threshold = threshold;
+        Ok(config)
+    })?;
 
-    let event = AddFoundryAssetEvent {
+    let event = SetThresholdEvent {
         from: info.sender.as_str(),
-        token: token.as_str(),
+        threshold,
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_remove_foundry_asset(
+pub fn execute_set_swap_fee(
     env: ExecuteEnv,
-    token: String,
+    swap_fee_bps: u16,
+    fee_distribution: Option<FeeDistribution>,
 ) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
@@ -192,22 +346,34 @@ pub fn execute_remove_foundry_asset(
     if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
+    if swap_fee_bps > MAX_SWAP_FEE_BPS {
+        return Err(ContractError::InvalidSwapFee {});
+    }
 
     let mut rsp = Response::default();
-    FOUNDRY_ASSETS.remove(deps.storage, token.as_str());
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.swap_fee_bps = swap_fee_bps;
+        if let Some(fee_distribution) = fee_distribution.clone() {
+            config.fee_distribution = fee_distribution;
+        }
+        Ok(config)
+    })?;
 
-    let event = RemoveFoundryAssetEvent {
+    let event = SetSwapFeeEvent {
         from: info.sender.as_str(),
-        token: token.as_str(),
+        swap_fee_bps,
+        fee_distribution: fee_distribution.as_ref().map(fee_distribution_name),
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_add_liquidity(
+pub fn execute_add_foundry_asset(
     env: ExecuteEnv,
     token: String,
-    amount: Uint128,
+    asset_info: Option<AssetInfo>,
+    symbol: Option<String>,
+    decimals: Option<u8>,
 ) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
@@ -215,43 +381,70 @@ pub fn execute_add_liquidity(
         info,
     } = env;
 
-    if !is_foundry_asset(deps.storage, token.to_string()) {
+    if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
+    let asset_info = asset_info.unwrap_or(AssetInfo::Native {
+        denom: token.to_string(),
+    });
+    if asset_info.token() != token {
+        return Err(ContractError::InvalidAssetInfo {});
+    }
+    let cw20 = matches!(asset_info, AssetInfo::Cw20 { .. });
+    if cw20 {
+        deps.api.addr_validate(&token)?;
+    }
 
     let mut rsp = Response::default();
-    LIQUIDITIES.update(
-        deps.storage,
-        (token.as_str(), &info.sender),
-        |liquidity: Option<Liquidity>| -> StdResult<_> {
-            if let Some(unwrapped) = liquidity {
-                return Ok(Liquidity {
-                    user: info.sender.to_string(),
-                    token: token.to_string(),
-                    amount: unwrapped.amount.checked_add(amount)?,
-                });
-            }
-            return Ok(Liquidity {
-                user: info.sender.to_string(),
-                token: token.to_string(),
-                amount: amount,
-            });
-        },
-    )?;
+    // adding a listed asset again keeps its limits and relists a delisted asset
+    let mut asset = FOUNDRY_ASSETS
+        .may_load(deps.storage, &token)?
+        .unwrap_or(FoundryAsset {
+            token: token.to_string(),
+            symbol: None,
+            decimals: None,
+            added_at: Some(env.block.height),
+            max_total_liquidity: None,
+            max_per_provider: None,
+            provider_allowlist: false,
+            status: AssetStatus::Active,
+            cw20,
+        });
+    // the token of a pool can not change
+    if asset.cw20 != cw20 {
+        return Err(ContractError::InvalidAssetInfo {});
+    }
+    asset.status = AssetStatus::Active;
+    asset.symbol = symbol.or(asset.symbol);
+    asset.decimals = decimals.or(asset.decimals);
+    if cw20 && (asset.symbol.is_none() || asset.decimals.is_none()) {
+        let info: TokenInfoResponse = deps
+            .querier
+            .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})?;
+        asset.symbol = asset.symbol.or(Some(info.symbol));
+        asset.decimals = asset.decimals.or(Some(info.decimals));
+    }
+    FOUNDRY_ASSETS.save(deps.storage, token.as_str(), &asset)?;
+    if let Some(code_id) = CONFIG.load(deps.storage)?.lp_token_code_id {
+        if !LP_TOKENS.has(deps.storage, &token) {
+            let instantiate = lp_token_instantiate(deps.storage, &env, code_id, &token)?;
+            rsp = rsp.add_submessage(instantiate);
+        }
+    }
 
-    let event = AddLiquidityEvent {
+    let event = AddFoundryAssetEvent {
         from: info.sender.as_str(),
         token: token.as_str(),
-        amount,
+        symbol: asset.symbol.as_deref(),
+        decimals: asset.decimals,
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_remove_liquidity(
+pub fn execute_remove_foundry_asset(
     env: ExecuteEnv,
     token: String,
-    amount: Uint128,
 ) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
@@ -259,254 +452,1883 @@ pub fn execute_remove_liquidity(
         info,
     } = env;
 
-    if !is_foundry_asset(deps.storage, token.to_string()) {
+    if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
 
     let mut rsp = Response::default();
-    LIQUIDITIES.update(
-        deps.storage,
-        (token.as_str(), &info.sender),
-        |liquidity: Option<Liquidity>| -> StdResult<_> {
-            if let Some(unwrapped) = liquidity {
-                return Ok(Liquidity {
-                    user: info.sender.to_string(),
-                    token: token.to_string(),
-                    amount: unwrapped.amount.checked_sub(amount)?,
-                });
-            }
-            return Err(StdError::generic_err("liquidity does not exist"));
-        },
-    )?;
+    let mut asset = FOUNDRY_ASSETS
+        .may_load(deps.storage, &token)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if asset.status == AssetStatus::Active {
+        // providers keep removing liquidity until the asset is empty
+        asset.status = AssetStatus::WithdrawOnly;
+        FOUNDRY_ASSETS.save(deps.storage, &token, &asset)?;
+
+        let event = DelistFoundryAssetEvent {
+            from: info.sender.as_str(),
+            token: token.as_str(),
+        };
+        event.add_attributes(&mut rsp);
+        return Ok(rsp);
+    }
 
-    let event = RemoveLiquidityEvent {
+    // shares wrapped into LP receipt tokens are only tracked by the pool total,
+    // the locked shares of the first deposit are never withdrawn
+    let pool = POOLS.may_load(deps.storage, &token)?.unwrap_or_default();
+    let has_positions = LIQUIDITIES
+        .prefix(&token)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if has_positions || pool.total_shares > pool.locked_shares {
+        return Err(ContractError::AssetHasLiquidity { token });
+    }
+    // unbonding liquidity is paid out of the asset until every claim is collected
+    if !pool.pending_claims.is_zero() {
+        return Err(ContractError::AssetHasClaims { token });
+    }
+    FOUNDRY_ASSETS.remove(deps.storage, token.as_str());
+
+    let event = RemoveFoundryAssetEvent {
         from: info.sender.as_str(),
         token: token.as_str(),
-        amount,
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_withdraw_signed(
+pub fn execute_update_asset_limits(
     env: ExecuteEnv,
-    payee: String,
     token: String,
-    amount: Uint128,
-    salt: String,
-    signature: String,
+    max_total_liquidity: Option<Uint128>,
+    max_per_provider: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    if !is_foundry_asset(env.deps.storage, token.to_string()) {
+    let ExecuteEnv { deps, env, info } = env;
+
+    if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    let payee_addr = env.deps.api.addr_validate(&payee)?;
+    let mut rsp = Response::default();
+    let mut asset = FOUNDRY_ASSETS
+        .may_load(deps.storage, &token)?
+        .ok_or(ContractError::Unauthorized {})?;
+    asset.max_total_liquidity = max_total_liquidity;
+    asset.max_per_provider = max_per_provider;
+    FOUNDRY_ASSETS.save(deps.storage, &token, &asset)?;
+
+    let event = UpdateAssetLimitsEvent {
+        from: info.sender.as_str(),
+        token: token.as_str(),
+        max_total_liquidity,
+        max_per_provider,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_add_liquidity_provider(
+    env: ExecuteEnv,
+    provider: String,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let provider_addr = deps.api.addr_validate(&provider)?;
+
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // TODO: gets signer from params
-    // signer, messageBytes, err := get_signer(ctx.ChainID(), payee, amount, salt, signature)
-    // if err != nil {
-    //     return types.ErrUnexpectedError(k.codespace, err)
-    // }
+    let mut rsp = Response::default();
+    LIQUIDITY_PROVIDERS.save(deps.storage, &provider_addr, &true)?;
 
-    // TODO: ensure that the signer is registered on-chain
-    // if is_signer(env.storage, signer.String()) {
-    //     return types.ErrInvalidSigner(k.codespace)
-    // }
+    let event = AddLiquidityProviderEvent {
+        from: info.sender.as_str(),
+        provider: provider.as_str(),
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
 
-    // TODO: avoid using same signature and salt again
-    // if k.IsUsedMessage(ctx, messageBytes) {
-    //     return types.ErrAlreadyUsedWithdrawMessage(k.codespace)
-    // }
+pub fn execute_remove_liquidity_provider(
+    env: ExecuteEnv,
+    provider: String,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let provider_addr = deps.api.addr_validate(&provider)?;
 
-    let bank_send_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: payee.to_string(),
-        amount: coins(amount.u128(), &token),
-    });
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    let ExecuteEnv {
-        mut deps,
-        env,
-        info,
-    } = env;
+    // existing positions are kept, only new deposits are refused
+    let mut rsp = Response::default();
+    LIQUIDITY_PROVIDERS.remove(deps.storage, &provider_addr);
 
-    let mut rsp = Response::new().add_message(bank_send_msg);
-    let event = BridgeWithdrawSignedEvent {
+    let event = RemoveLiquidityProviderEvent {
         from: info.sender.as_str(),
-        payee: payee.as_str(),
-        token: token.as_str(),
-        amount,
-        salt: &salt,
-        signature: &signature,
+        provider: provider.as_str(),
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-pub fn execute_swap(
+pub fn execute_set_provider_allowlist(
     env: ExecuteEnv,
-    token: String,
-    amount: Uint128,
-    target_chain_id: String,
-    target_token: String,
-    target_address: String,
+    token: Option<String>,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut rsp = Response::default();
+    if let Some(token) = token.as_deref() {
+        let mut asset = FOUNDRY_ASSETS
+            .may_load(deps.storage, token)?
+            .ok_or(ContractError::Unauthorized {})?;
+        asset.provider_allowlist = enabled;
+        FOUNDRY_ASSETS.save(deps.storage, token, &asset)?;
+    } else {
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.provider_allowlist = enabled;
+            Ok(config)
+        })?;
+    }
+
+    let event = SetProviderAllowlistEvent {
+        from: info.sender.as_str(),
+        token: token.as_deref(),
+        enabled,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_approve_operator(
+    env: ExecuteEnv,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let operator_addr = deps.api.addr_validate(&operator)?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ApprovalExpired {});
+    }
+
+    let mut rsp = Response::default();
+    OPERATORS.save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+
+    let event = ApproveOperatorEvent {
+        owner: info.sender.as_str(),
+        operator: operator.as_str(),
+        expires,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_revoke_operator(
+    env: ExecuteEnv,
+    operator: String,
 ) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+    let operator_addr = deps.api.addr_validate(&operator)?;
+
     let mut rsp = Response::default();
+    OPERATORS.remove(deps.storage, (&info.sender, &operator_addr));
+
+    let event = RevokeOperatorEvent {
+        owner: info.sender.as_str(),
+        operator: operator.as_str(),
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+/// load_liquidity_owner returns the owner of the position a liquidity call acts on,
+/// the sender itself unless it is an unexpired operator of `owner`
+fn load_liquidity_owner(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    owner: Option<String>,
+) -> Result<Addr, ContractError> {
+    let owner = match owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => return Ok(sender.clone()),
+    };
+    if owner == *sender {
+        return Ok(owner);
+    }
+    match OPERATORS.may_load(deps.storage, (&owner, sender))? {
+        Some(expires) if !expires.is_expired(&env.block) => Ok(owner),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
 
+pub fn execute_add_liquidity(
+    env: ExecuteEnv,
+    asset_info: AssetInfo,
+    amount: Uint128,
+    owner: Option<String>,
+) -> Result<Response, ContractError> {
     let ExecuteEnv {
         mut deps,
         env,
         info,
     } = env;
 
-    // token deposit verification
-    let funds = info.funds;
-    if funds.len() != 1 {
-        return Err(ContractError::InvalidDeposit {});
-    }
-    if funds[0].denom != token {
-        return Err(ContractError::InvalidDeposit {});
+    let token = verify_native_asset(deps.storage, &asset_info)?;
+    verify_deposit(&info.funds, token, amount)?;
+    add_liquidity(deps.branch(), &env, &info.sender, token, amount, owner)
+}
+
+/// add_liquidity mints pool shares for a verified deposit of `sender`,
+/// an operator deposits its own funds into the position of the owner
+fn add_liquidity(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    token: &str,
+    amount: Uint128,
+    owner: Option<String>,
+) -> Result<Response, ContractError> {
+    let owner = load_liquidity_owner(deps.as_ref(), env, sender, owner)?;
+    let asset = load_active_foundry_asset(deps.storage, token)?;
+    verify_liquidity_provider(deps.storage, &asset, &owner)?;
+
+    let mut rsp = Response::default();
+    let mut pool = POOLS.may_load(deps.storage, token)?.unwrap_or_default();
+    verify_liquidity_limits(deps.as_ref(), &asset, &pool, &owner, amount)?;
+    // the first provider sets the share price, fees retained before are credited to it.
+    // Part of its shares is locked so a tiny first deposit can not inflate the share
+    // price and round the shares of later deposits down to nothing.
+    let (shares, locked) = if pool.total_shares.is_zero() {
+        let shares = amount.saturating_sub(MINIMUM_LIQUIDITY);
+        (shares, MINIMUM_LIQUIDITY)
+    } else {
+        let shares = amount.multiply_ratio(pool.total_shares, pool.total_liquidity);
+        (shares, Uint128::zero())
+    };
+    if shares.is_zero() {
+        return Err(ContractError::InvalidLiquidityShares {});
     }
-    if funds[0].amount != amount {
-        return Err(ContractError::InvalidDeposit {});
+    pool.locked_shares = pool.locked_shares.checked_add(locked)?;
+    pool.total_shares = pool.total_shares.checked_add(shares)?.checked_add(locked)?;
+    pool.total_liquidity = pool.total_liquidity.checked_add(amount)?;
+    POOLS.save(deps.storage, token, &pool, env.block.height)?;
+    increase_available_liquidity(deps.storage, token, amount)?;
+    if let Some(lp_token) = LP_TOKENS.may_load(deps.storage, token)? {
+        rsp = rsp.add_message(lp_token_mint(&lp_token, &owner, shares)?);
+    } else {
+        let balance = read_liquidity_shares(deps.storage, token, &owner)?;
+        save_liquidity_shares(
+            deps.storage,
+            token,
+            &owner,
+            balance.checked_add(shares)?,
+            env.block.height,
+        )?;
     }
 
-    let event = BridgeSwapEvent {
-        from: info.sender.as_str(),
-        token: token.as_str(),
+    let event = AddLiquidityEvent {
+        from: owner.as_str(),
+        token,
         amount,
-        target_chain_id: &target_chain_id,
-        target_token: &target_token,
-        target_address: &target_address,
+        shares,
+        operator: Some(sender.as_str()).filter(|_| owner != *sender),
     };
     event.add_attributes(&mut rsp);
     Ok(rsp)
 }
 
-// TODO: get_signer calculate signer from withdraw signed message parameters
-// pub fn get_signer(chainId string, payee string, amount sdk.Coin,
-//     salt string, signature []byte) (common.Address, []byte, error) {
-//     signer := common.Address{}
-
-//     // get sign message to be used for signature verification
-//     message := &types.WithdrawSignMessage{
-//         ChainId: chainId,
-//         Payee:   payee,
-//         Amount:  amount,
-//         Salt:    salt,
-//     }
-//     messageBytes, err := json.Marshal(message)
-//     if err != nil {
-//         return signer, messageBytes, err
-//     }
-
-//     // get signer from sign message and signature
-//     if len(signature) > crypto.RecoveryIDOffset {
-//         signature[crypto.RecoveryIDOffset] -= 27 // Transform yellow paper V from 27/28 to 0/1
-//         recovered, err := crypto.SigToPub(accounts.TextHash(messageBytes), signature)
-//         if err != nil {
-//             return signer, messageBytes, err
-//         }
-//         signer = crypto.PubkeyToAddress(*recovered)
-//     }
-
-//     return signer, messageBytes, nil
-// }
+pub fn execute_remove_liquidity(
+    env: ExecuteEnv,
+    token: String,
+    shares: Uint128,
+    owner: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        env,
+        info,
+    } = env;
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: MultiswapQueryMsg) -> StdResult<Binary> {
-    match msg {
-        MultiswapQueryMsg::Liquidity { owner, token } => {
-            to_binary(&query_liquidity(deps, owner, token)?)
-        }
-        MultiswapQueryMsg::AllLiquidity {} => to_binary(&query_all_liquidity(deps)?),
-        MultiswapQueryMsg::Owner {} => to_binary(&query_owner(deps)?),
-        MultiswapQueryMsg::Signers {} => to_binary(&query_signers(deps)?),
-        MultiswapQueryMsg::FoundryAssets {} => to_binary(&query_foundry_assets(deps)?),
+    // removed liquidity is always paid out to the owner, never to its operator
+    let owner = load_liquidity_owner(deps.as_ref(), &env, &info.sender, owner)?;
+    let balance = read_liquidity_shares(deps.storage, &token, &owner)?;
+    if balance.is_zero() {
+        return Err(StdError::generic_err("liquidity does not exist").into());
     }
+    let remaining = balance.checked_sub(shares)?;
+    let operator = Some(&info.sender).filter(|sender| **sender != owner);
+    let rsp = remove_liquidity(deps.branch(), &env, &token, shares, &owner, operator)?;
+    save_liquidity_shares(deps.storage, &token, &owner, remaining, env.block.height)?;
+    Ok(rsp)
 }
 
-pub fn query_owner(deps: Deps) -> StdResult<String> {
-    let owner = OWNER.load(deps.storage)?;
-    return Ok(owner.to_string());
+fn read_liquidity_shares(storage: &dyn Storage, token: &str, owner: &Addr) -> StdResult<Uint128> {
+    let position = LIQUIDITIES.may_load(storage, (token, owner))?;
+    Ok(position.map(|position| position.shares).unwrap_or_default())
 }
 
-pub fn query_liquidity(deps: Deps, owner: String, token: String) -> StdResult<Liquidity> {
-    let owner_addr = deps.api.addr_validate(&owner)?;
-    if let Ok(Some(liquidity)) = LIQUIDITIES.may_load(deps.storage, (&token, &owner_addr)) {
-        return Ok(liquidity);
+/// save_liquidity_shares stores the shares an owner holds in the pool after settling its
+/// rewards. Owners without shares are no longer counted as providers and are removed once
+/// their rewards are claimed.
+fn save_liquidity_shares(
+    storage: &mut dyn Storage,
+    token: &str,
+    owner: &Addr,
+    shares: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    let mut pool = POOLS.may_load(storage, token)?.unwrap_or_default();
+    let mut position = LIQUIDITIES
+        .may_load(storage, (token, owner))?
+        .unwrap_or_default();
+    settle_rewards(&pool, &mut position)?;
+    if position.shares.is_zero() && !shares.is_zero() {
+        pool.provider_count += 1;
     }
-    return Err(StdError::generic_err("liquidity does not exist"));
+    if !position.shares.is_zero() && shares.is_zero() {
+        pool.provider_count -= 1;
+    }
+    pool.reward_shares = pool
+        .reward_shares
+        .checked_sub(position.shares)?
+        .checked_add(shares)?;
+    position.shares = shares;
+    save_liquidity_position(storage, token, owner, &position, height)?;
+    POOLS.save(storage, token, &pool, height)
 }
 
-pub fn query_all_liquidity(deps: Deps) -> StdResult<Vec<Liquidity>> {
-    // Ok(LIQUIDITIES.may_load(deps.storage)?.unwrap_or_default())
-    // Err(StdError::generic_err("not implemented yet"))
-    // let limit: u32 = 30;
-    return read_liquidities(deps.storage, deps.api);
+fn save_liquidity_position(
+    storage: &mut dyn Storage,
+    token: &str,
+    owner: &Addr,
+    position: &LiquidityPosition,
+    height: u64,
+) -> StdResult<()> {
+    if position.shares.is_zero() && position.pending_rewards.is_zero() {
+        return LIQUIDITIES.remove(storage, (token, owner), height);
+    }
+    LIQUIDITIES.save(storage, (token, owner), position, height)
 }
 
-pub fn query_signers(deps: Deps) -> StdResult<Vec<String>> {
-    Ok(read_signers(deps.storage, deps.api))
+/// settle_rewards moves the rewards accrued since the last settlement to the pending rewards
+fn settle_rewards(pool: &LiquidityPool, position: &mut LiquidityPosition) -> StdResult<()> {
+    let accrued = position.shares * (pool.reward_index - position.reward_index);
+    position.pending_rewards = position.pending_rewards.checked_add(accrued)?;
+    position.reward_index = pool.reward_index;
+    Ok(())
 }
 
-pub fn query_foundry_assets(deps: Deps) -> StdResult<Vec<String>> {
-    Ok(read_foundry_assets(deps.storage, deps.api))
-}
+pub fn execute_claim_rewards(env: ExecuteEnv, token: String) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
 
-const MAX_LIMIT: u32 = 30;
-const DEFAULT_LIMIT: u32 = 10;
-pub fn read_liquidities(
-    storage: &dyn Storage,
-    api: &dyn Api,
-    // start_after: Option<(String, String)>,
-    // limit: Option<u32>,
-) -> StdResult<Vec<Liquidity>> {
-    let limit = DEFAULT_LIMIT as usize;
-    // let start = calc_range_start(start_after);
-    // let start_key = start.map(Bound::exclusive);
+    let pool = POOLS.may_load(deps.storage, &token)?.unwrap_or_default();
+    let mut position = LIQUIDITIES
+        .may_load(deps.storage, (&token, &info.sender))?
+        .unwrap_or_default();
+    settle_rewards(&pool, &mut position)?;
+    let amount = position.pending_rewards;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    position.pending_rewards = Uint128::zero();
+    save_liquidity_position(
+        deps.storage,
+        &token,
+        &info.sender,
+        &position,
+        env.block.height,
+    )?;
 
-    LIQUIDITIES
-        .range(storage, None, None, Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            let (_, v) = item?;
-            v.to_normal(api)
-        })
-        .collect::<StdResult<Vec<Liquidity>>>()
+    let asset_info = load_asset_info(deps.storage, &token)?;
+    verify_pool_balance(deps.as_ref(), &env, &token, &asset_info, amount)?;
+    decrease_available_liquidity(deps.storage, &token, amount)?;
+
+    let mut rsp = Response::new().add_message(asset_transfer(&asset_info, &info.sender, amount)?);
+    let event = ClaimRewardsEvent {
+        from: info.sender.as_str(),
+        token: token.as_str(),
+        amount,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
 }
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start(start_after: Option<(String, String)>) -> Option<Vec<u8>> {
-    start_after.map(|info| {
-        let mut v = [info.0.as_bytes(), info.1.as_bytes()]
-            .concat()
-            .as_slice()
-            .to_vec();
-        v.push(1);
-        v
+/// remove_liquidity redeems pool shares and pays the underlying liquidity to the recipient,
+/// or creates a claim if the asset has an unbonding period.
+/// The caller is responsible for burning the shares.
+fn remove_liquidity(
+    deps: DepsMut,
+    env: &Env,
+    token: &str,
+    shares: Uint128,
+    recipient: &Addr,
+    operator: Option<&Addr>,
+) -> Result<Response, ContractError> {
+    if !is_foundry_asset(deps.storage, token.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pool = POOLS.load(deps.storage, token)?;
+    let amount = shares.multiply_ratio(pool.total_liquidity, pool.total_shares);
+    if amount.is_zero() {
+        return Err(ContractError::InvalidLiquidityShares {});
+    }
+
+    let asset_info = load_asset_info(deps.storage, token)?;
+    let release_at = UNBONDING_PERIODS
+        .may_load(deps.storage, token)?
+        .map(|period| period.after(&env.block));
+    if release_at.is_none() {
+        verify_pool_balance(deps.as_ref(), env, token, &asset_info, amount)?;
+    }
+
+    pool.total_shares = pool.total_shares.checked_sub(shares)?;
+    pool.total_liquidity = pool.total_liquidity.checked_sub(amount)?;
+    if release_at.is_some() {
+        pool.pending_claims = pool.pending_claims.checked_add(amount)?;
+    }
+    POOLS.save(deps.storage, token, &pool, env.block.height)?;
+
+    let mut rsp = Response::new();
+    if let Some(release_at) = release_at {
+        // unbonding liquidity keeps backing bridge withdrawals until it is claimed
+        let claim = Claim {
+            token: token.to_string(),
+            amount,
+            release_at,
+            asset_info: Some(asset_info),
+        };
+        CLAIMS.update(deps.storage, recipient, |claims| -> StdResult<_> {
+            let mut claims = claims.unwrap_or_default();
+            claims.push(claim);
+            Ok(claims)
+        })?;
+    } else {
+        decrease_available_liquidity(deps.storage, token, amount)?;
+        rsp = rsp.add_message(asset_transfer(&asset_info, recipient, amount)?);
+    }
+
+    let event = RemoveLiquidityEvent {
+        from: recipient.as_str(),
+        token,
+        amount,
+        shares,
+        release_at,
+        operator: operator.map(Addr::as_str),
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+/// verify_liquidity_provider checks the provider is allowlisted if the asset or contract requires it
+fn verify_liquidity_provider(
+    storage: &dyn Storage,
+    asset: &FoundryAsset,
+    provider: &Addr,
+) -> Result<(), ContractError> {
+    if !asset.provider_allowlist && !CONFIG.load(storage)?.provider_allowlist {
+        return Ok(());
+    }
+    if !LIQUIDITY_PROVIDERS.has(storage, provider) {
+        return Err(ContractError::LiquidityProviderNotAllowed {});
+    }
+    Ok(())
+}
+
+/// verify_liquidity_limits checks that a deposit stays within the caps of the asset
+fn verify_liquidity_limits(
+    deps: Deps,
+    asset: &FoundryAsset,
+    pool: &LiquidityPool,
+    provider: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if let Some(cap) = asset.max_total_liquidity {
+        let requested = pool.total_liquidity.checked_add(amount)?;
+        if requested > cap {
+            return Err(ContractError::LiquidityCapExceeded {
+                token: asset.token.to_string(),
+                cap,
+                requested,
+            });
+        }
+    }
+    if let Some(cap) = asset.max_per_provider {
+        // shares wrapped into LP receipt tokens still count towards the provider cap
+        let mut shares = read_liquidity_shares(deps.storage, &asset.token, provider)?;
+        if let Some(lp_token) = LP_TOKENS.may_load(deps.storage, &asset.token)? {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                lp_token,
+                &Cw20QueryMsg::Balance {
+                    address: provider.to_string(),
+                },
+            )?;
+            shares = shares.checked_add(balance.balance)?;
+        }
+        let provided = if pool.total_shares.is_zero() {
+            Uint128::zero()
+        } else {
+            shares.multiply_ratio(pool.total_liquidity, pool.total_shares)
+        };
+        let requested = provided.checked_add(amount)?;
+        if requested > cap {
+            return Err(ContractError::ProviderLiquidityCapExceeded {
+                token: asset.token.to_string(),
+                cap,
+                requested,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// verify_pool_balance guards payouts against bridge withdrawals that drained the pool
+/// below the provided liquidity
+fn verify_pool_balance(
+    deps: Deps,
+    env: &Env,
+    token: &str,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let balance = query_asset_balance(deps, env, asset_info)?;
+    if balance < amount {
+        return Err(ContractError::InsufficientLiquidity {
+            token: token.to_string(),
+            available: balance,
+            requested: amount,
+        });
+    }
+    Ok(())
+}
+
+/// load_asset_info returns how a token is held, tokens no longer listed are bank denoms
+fn load_asset_info(storage: &dyn Storage, token: &str) -> StdResult<AssetInfo> {
+    let asset = FOUNDRY_ASSETS.may_load(storage, token)?;
+    Ok(asset
+        .map(|asset| asset.asset_info())
+        .unwrap_or(AssetInfo::Native {
+            denom: token.to_string(),
+        }))
+}
+
+/// query_asset_balance returns the balance of the contract in a bank denom or cw20 token
+/// verify_asset_info checks `asset_info` matches how its token is held
+fn verify_asset_info(storage: &dyn Storage, asset_info: &AssetInfo) -> Result<(), ContractError> {
+    if load_asset_info(storage, asset_info.token())? != *asset_info {
+        return Err(ContractError::InvalidAssetInfo {});
+    }
+    Ok(())
+}
+
+/// verify_native_asset checks `asset_info` is a bank denom deposited as attached funds,
+/// cw20 assets are deposited through `Receive`. Returns the token of the asset.
+fn verify_native_asset<'a>(
+    storage: &dyn Storage,
+    asset_info: &'a AssetInfo,
+) -> Result<&'a str, ContractError> {
+    if !matches!(asset_info, AssetInfo::Native { .. }) {
+        return Err(ContractError::InvalidAssetInfo {});
+    }
+    verify_asset_info(storage, asset_info)?;
+    Ok(asset_info.token())
+}
+
+fn query_asset_balance(deps: Deps, env: &Env, asset_info: &AssetInfo) -> StdResult<Uint128> {
+    match asset_info {
+        AssetInfo::Native { denom } => {
+            let balance = deps
+                .querier
+                .query_balance(env.contract.address.to_string(), denom.to_string())?;
+            Ok(balance.amount)
+        }
+        AssetInfo::Cw20 { contract_addr } => {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(balance.balance)
+        }
+    }
+}
+
+/// asset_transfer pays `amount` of a bank denom or cw20 token to the recipient
+fn asset_transfer(
+    asset_info: &AssetInfo,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match asset_info {
+        AssetInfo::Native { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        })),
+        AssetInfo::Cw20 { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+pub fn execute_set_unbonding_period(
+    env: ExecuteEnv,
+    token: String,
+    unbonding_period: Option<Duration>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !is_foundry_asset(deps.storage, token.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut rsp = Response::default();
+    match unbonding_period {
+        Some(period) => UNBONDING_PERIODS.save(deps.storage, &token, &period)?,
+        None => UNBONDING_PERIODS.remove(deps.storage, &token),
+    }
+
+    let event = SetUnbondingPeriodEvent {
+        from: info.sender.as_str(),
+        token: token.as_str(),
+        unbonding_period,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_claim_liquidity(env: ExecuteEnv) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    let claims = CLAIMS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let (matured, pending): (Vec<Claim>, Vec<Claim>) = claims
+        .into_iter()
+        .partition(|claim| claim.release_at.is_expired(&env.block));
+    if matured.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    if pending.is_empty() {
+        CLAIMS.remove(deps.storage, &info.sender);
+    } else {
+        CLAIMS.save(deps.storage, &info.sender, &pending)?;
+    }
+
+    let mut amounts: BTreeMap<String, (AssetInfo, Uint128)> = BTreeMap::new();
+    for claim in matured {
+        let asset_info = match claim.asset_info {
+            Some(asset_info) => asset_info,
+            None => load_asset_info(deps.storage, &claim.token)?,
+        };
+        let (_, amount) = amounts
+            .entry(claim.token)
+            .or_insert((asset_info, Uint128::zero()));
+        *amount = amount.checked_add(claim.amount)?;
+    }
+    let mut rsp = Response::new();
+    let mut payout = vec![];
+    let mut native = vec![];
+    for (token, (asset_info, amount)) in amounts {
+        verify_pool_balance(deps.as_ref(), &env, &token, &asset_info, amount)?;
+        decrease_available_liquidity(deps.storage, &token, amount)?;
+        let mut pool = POOLS.load(deps.storage, &token)?;
+        pool.pending_claims = pool.pending_claims.checked_sub(amount)?;
+        POOLS.save(deps.storage, &token, &pool, env.block.height)?;
+        // bank denoms are paid with a single send, cw20 tokens with a transfer each
+        match asset_info {
+            AssetInfo::Native { .. } => native.push(Coin {
+                denom: token.to_string(),
+                amount,
+            }),
+            asset_info => rsp = rsp.add_message(asset_transfer(&asset_info, &info.sender, amount)?),
+        }
+        payout.push(Coin {
+            denom: token,
+            amount,
+        });
+    }
+    if !native.is_empty() {
+        rsp = rsp.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: native,
+        });
+    }
+    let event = ClaimLiquidityEvent {
+        from: info.sender.as_str(),
+        amount: &payout,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_create_lp_token(env: ExecuteEnv, token: String) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !is_foundry_asset(deps.storage, token.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if LP_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::LpTokenExists {});
+    }
+    let code_id = CONFIG
+        .load(deps.storage)?
+        .lp_token_code_id
+        .ok_or(ContractError::LpTokenNotConfigured {})?;
+
+    let instantiate = lp_token_instantiate(deps.storage, &env, code_id, &token)?;
+    Ok(Response::new()
+        .add_submessage(instantiate)
+        .add_attribute("action", "create_lp_token")
+        .add_attribute("token", token))
+}
+
+pub fn execute_wrap_liquidity(
+    env: ExecuteEnv,
+    token: String,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    let lp_token = LP_TOKENS
+        .may_load(deps.storage, &token)?
+        .ok_or(ContractError::LpTokenNotConfigured {})?;
+    let balance = read_liquidity_shares(deps.storage, &token, &info.sender)?;
+    if balance.is_zero() {
+        return Err(StdError::generic_err("liquidity does not exist").into());
+    }
+    let remaining = balance.checked_sub(shares)?;
+    save_liquidity_shares(
+        deps.storage,
+        &token,
+        &info.sender,
+        remaining,
+        env.block.height,
+    )?;
+
+    let mut rsp = Response::new().add_message(lp_token_mint(&lp_token, &info.sender, shares)?);
+    let event = WrapLiquidityEvent {
+        from: info.sender.as_str(),
+        token: token.as_str(),
+        shares,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn execute_receive(env: ExecuteEnv, msg: Cw20ReceiveMsg) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    let sender = deps.api.addr_validate(&msg.sender)?;
+    // LP receipt tokens are redeemed, cw20 foundry assets are deposited
+    if let Some(token) = LP_TOKEN_ASSETS.may_load(deps.storage, &info.sender)? {
+        return match from_binary(&msg.msg)? {
+            Cw20HookMsg::RemoveLiquidity {} => {
+                let rsp = remove_liquidity(deps, &env, &token, msg.amount, &sender, None)?;
+                Ok(rsp.add_message(WasmMsg::Execute {
+                    contract_addr: info.sender.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Burn { amount: msg.amount })?,
+                    funds: vec![],
+                }))
+            }
+            _ => Err(ContractError::Unauthorized {}),
+        };
+    }
+
+    let token = info.sender.to_string();
+    let asset = load_active_foundry_asset(deps.storage, &token)?;
+    if !asset.cw20 {
+        return Err(ContractError::Unauthorized {});
+    }
+    match from_binary(&msg.msg)? {
+        Cw20HookMsg::AddLiquidity { owner } => {
+            add_liquidity(deps, &env, &sender, &token, msg.amount, owner)
+        }
+        Cw20HookMsg::Swap {
+            target_chain_id,
+            target_token,
+            target_address,
+        } => swap(
+            deps,
+            &env,
+            &sender,
+            &token,
+            msg.amount,
+            &target_chain_id,
+            &target_token,
+            &target_address,
+        ),
+        Cw20HookMsg::RemoveLiquidity {} => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// lp_token_instantiate creates the LP receipt token of a foundry asset, the token is
+/// registered when its instantiation reply is received
+fn lp_token_instantiate(
+    storage: &mut dyn Storage,
+    env: &Env,
+    code_id: u64,
+    token: &str,
+) -> StdResult<SubMsg> {
+    let name: String = format!("Multiswap LP {}", token).chars().take(50).collect();
+    let msg = Cw20InstantiateMsg {
+        name,
+        symbol: LP_TOKEN_SYMBOL.to_string(),
+        decimals: LP_TOKEN_DECIMALS,
+        initial_balances: vec![],
+        mint: Some(MinterResponse {
+            minter: env.contract.address.to_string(),
+            cap: None,
+        }),
+    };
+    let id = next_reply_id(storage)?;
+    LP_TOKEN_INSTANTIATES.save(storage, id, &token.to_string())?;
+    Ok(SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(env.contract.address.to_string()),
+            code_id,
+            msg: to_binary(&msg)?,
+            funds: vec![],
+            label: format!("multiswap lp {}", token),
+        },
+        id,
+    ))
+}
+
+fn lp_token_mint(lp_token: &Addr, recipient: &Addr, shares: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: shares,
+        })?,
+        funds: vec![],
+    })
+}
+
+pub fn execute_withdraw_signed(
+    env: ExecuteEnv,
+    item: WithdrawSignedItem,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    let config = CONFIG.load(deps.storage)?;
+    let (message_hash, signers) =
+        verify_withdraw_signed(deps.storage, deps.api, &env, &config, &item)?;
+    USED_MESSAGES.save(deps.storage, &message_hash, &true)?;
+    decrease_available_liquidity(deps.storage, item.asset_info.token(), item.amount)?;
+    increase_bridged_out(
+        deps.storage,
+        item.asset_info.token(),
+        item.amount,
+        env.block.height,
+    )?;
+
+    let payout = withdraw_payout(deps.storage, &item)?;
+
+    let mut rsp = Response::new().add_submessage(payout);
+    add_withdraw_signed_event(&mut rsp, &info, &item, &signers);
+    Ok(rsp)
+}
+
+pub fn execute_withdraw_signed_batch(
+    env: ExecuteEnv,
+    items: Vec<WithdrawSignedItem>,
+    skip_processed: bool,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, env, info } = env;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut rsp = Response::new();
+    // payouts aggregated per payee and denom
+    let mut payouts: BTreeMap<String, BTreeMap<String, Uint128>> = BTreeMap::new();
+    for item in items.iter() {
+        let (message_hash, signers) =
+            match verify_withdraw_signed(deps.storage, deps.api, &env, &config, item) {
+                Err(ContractError::AlreadyUsedWithdrawMessage {}) if skip_processed => {
+                    rsp.attributes.push(attr("skipped", item.salt.to_string()));
+                    continue;
+                }
+                result => result?,
+            };
+        USED_MESSAGES.save(deps.storage, &message_hash, &true)?;
+        decrease_available_liquidity(deps.storage, item.asset_info.token(), item.amount)?;
+        increase_bridged_out(
+            deps.storage,
+            item.asset_info.token(),
+            item.amount,
+            env.block.height,
+        )?;
+
+        // payee calls can not be aggregated
+        if item.msg.is_some() {
+            rsp = rsp.add_submessage(withdraw_payout(deps.storage, item)?);
+            add_withdraw_signed_event(&mut rsp, &info, item, &signers);
+            continue;
+        }
+        let amount = payouts
+            .entry(item.payee.to_string())
+            .or_default()
+            .entry(item.asset_info.token().to_string())
+            .or_default();
+        *amount = amount.checked_add(item.amount)?;
+
+        add_withdraw_signed_event(&mut rsp, &info, item, &signers);
+    }
+
+    for (payee, amounts) in payouts {
+        let payee = Addr::unchecked(payee);
+        let mut native = vec![];
+        for (token, amount) in amounts {
+            match load_asset_info(deps.storage, &token)? {
+                AssetInfo::Native { denom } => native.push(Coin { denom, amount }),
+                asset_info => rsp = rsp.add_message(asset_transfer(&asset_info, &payee, amount)?),
+            }
+        }
+        if !native.is_empty() {
+            rsp = rsp.add_message(CosmosMsg::Bank(BankMsg::Send {
+                to_address: payee.to_string(),
+                amount: native,
+            }));
+        }
+    }
+    Ok(rsp)
+}
+
+/// verify_withdraw_signed checks a signed withdrawal without marking it as used.
+/// Returns the signed message hash and the distinct registered signers.
+pub fn verify_withdraw_signed(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    env: &Env,
+    config: &Config,
+    item: &WithdrawSignedItem,
+) -> Result<(Vec<u8>, Vec<String>), ContractError> {
+    load_active_foundry_asset(storage, item.asset_info.token())?;
+    verify_asset_info(storage, &item.asset_info)?;
+
+    api.addr_validate(&item.payee)?;
+
+    if item.expires_at.is_expired(&env.block) {
+        return Err(ContractError::SignatureExpired {});
+    }
+
+    // gets signers from params
+    let message = withdraw_sign_message(env, item);
+    let message_hash = get_message_hash(&config.sign_mode, env, &message)?;
+    // ensure that the signers are registered on-chain and reach the threshold
+    let signers = get_signers(storage, api, env, &message_hash, &item.signatures)?;
+    if signers.len() < config.threshold as usize {
+        return Err(ContractError::InsufficientSignatures {});
+    }
+
+    // avoid using same signature and salt again
+    if is_used_message(storage, &message_hash) {
+        return Err(ContractError::AlreadyUsedWithdrawMessage {});
+    }
+    Ok((message_hash, signers))
+}
+
+/// withdraw_sign_message builds the message signed for a withdrawal on this chain
+pub fn withdraw_sign_message(env: &Env, item: &WithdrawSignedItem) -> WithdrawSignMessage {
+    WithdrawSignMessage {
+        chain_id: env.block.chain_id.to_string(),
+        payee: item.payee.to_string(),
+        token: item.asset_info.token().to_string(),
+        amount: item.amount,
+        salt: item.salt.to_string(),
+        expires_at: item.expires_at,
+        msg: item.msg.clone(),
+    }
+}
+
+/// withdraw_payout sends the withdrawn funds to the payee, executing the payee contract
+/// with the funds attached when the withdrawal carries a msg. cw20 tokens are sent to the
+/// payee contract with the msg as `send` hook instead.
+/// A failing payee call falls back to a plain transfer in `reply`.
+fn withdraw_payout(storage: &mut dyn Storage, item: &WithdrawSignedItem) -> StdResult<SubMsg> {
+    let asset_info = item.asset_info.clone();
+    let payee = Addr::unchecked(&item.payee);
+    let msg = match &item.msg {
+        Some(msg) => msg,
+        None => {
+            return Ok(SubMsg::new(asset_transfer(
+                &asset_info,
+                &payee,
+                item.amount,
+            )?))
+        }
+    };
+
+    let id = next_reply_id(storage)?;
+    let amount = coins(item.amount.u128(), item.asset_info.token());
+    let call = WithdrawCall {
+        payee: item.payee.to_string(),
+        amount: amount.clone(),
+    };
+    WITHDRAW_CALLS.save(storage, id, &call)?;
+    let execute_msg = match asset_info {
+        AssetInfo::Native { .. } => WasmMsg::Execute {
+            contract_addr: item.payee.to_string(),
+            msg: msg.clone(),
+            funds: amount,
+        },
+        AssetInfo::Cw20 { contract_addr } => WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: item.payee.to_string(),
+                amount: item.amount,
+                msg: msg.clone(),
+            })?,
+            funds: vec![],
+        },
+    };
+    Ok(SubMsg::reply_always(execute_msg, id))
+}
+
+fn next_reply_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = REPLY_SEQ.may_load(storage)?.unwrap_or_default() + 1;
+    REPLY_SEQ.save(storage, &id)?;
+    Ok(id)
+}
+
+fn add_withdraw_signed_event(
+    rsp: &mut Response,
+    info: &MessageInfo,
+    item: &WithdrawSignedItem,
+    signers: &[String],
+) {
+    let signatures: Vec<String> = item
+        .signatures
+        .iter()
+        .map(|signature| signature.signature.to_string())
+        .collect();
+    let event = BridgeWithdrawSignedEvent {
+        from: info.sender.as_str(),
+        payee: item.payee.as_str(),
+        token: item.asset_info.token(),
+        amount: item.amount,
+        salt: &item.salt,
+        signatures: &signatures,
+        signers,
+    };
+    event.add_attributes(rsp);
+}
+
+pub fn execute_swap(
+    env: ExecuteEnv,
+    asset_info: AssetInfo,
+    amount: Uint128,
+    target_chain_id: String,
+    target_token: String,
+    target_address: String,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        env,
+        info,
+    } = env;
+
+    // token deposit verification
+    let token = verify_native_asset(deps.storage, &asset_info)?;
+    verify_deposit(&info.funds, token, amount)?;
+    swap(
+        deps.branch(),
+        &env,
+        &info.sender,
+        token,
+        amount,
+        &target_chain_id,
+        &target_token,
+        &target_address,
+    )
+}
+
+/// swap bridges a verified deposit of `sender` to the target chain
+#[allow(clippy::too_many_arguments)]
+fn swap(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    token: &str,
+    amount: Uint128,
+    target_chain_id: &str,
+    target_token: &str,
+    target_address: &str,
+) -> Result<Response, ContractError> {
+    let mut rsp = Response::default();
+    load_active_foundry_asset(deps.storage, token)?;
+
+    // swapped tokens stay in the pool and back withdrawals
+    increase_available_liquidity(deps.storage, token, amount)?;
+
+    // the fee is kept by the pool and only the remainder is bridged
+    let config = CONFIG.load(deps.storage)?;
+    let fee = amount.multiply_ratio(config.swap_fee_bps, MAX_SWAP_FEE_BPS);
+    let mut pool = POOLS.may_load(deps.storage, token)?.unwrap_or_default();
+    match config.fee_distribution {
+        // shares wrapped into LP receipt tokens do not earn rewards, their part of the fee
+        // compounds into the pool
+        FeeDistribution::RewardIndex if !pool.reward_shares.is_zero() => {
+            let reward = fee.multiply_ratio(pool.reward_shares, pool.total_shares);
+            let index = Decimal::checked_from_ratio(reward, pool.reward_shares)
+                .map_err(|_| ContractError::RewardIndexOverflow {})?;
+            pool.reward_index =
+                Decimal::new(pool.reward_index.atomics().checked_add(index.atomics())?);
+            pool.total_liquidity = pool.total_liquidity.checked_add(fee - reward)?;
+        }
+        // fees compound into the pool when no shares earn rewards
+        _ => pool.total_liquidity = pool.total_liquidity.checked_add(fee)?,
+    }
+    pool.total_bridged_in = pool.total_bridged_in.checked_add(amount)?;
+    POOLS.save(deps.storage, token, &pool, env.block.height)?;
+
+    let event = BridgeSwapEvent {
+        from: sender.as_str(),
+        token,
+        amount: amount - fee,
+        fee,
+        target_chain_id,
+        target_token,
+        target_address,
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+/// verify_deposit checks that exactly `amount` of `token` is attached and nothing else
+pub fn verify_deposit(funds: &[Coin], token: &str, amount: Uint128) -> Result<(), ContractError> {
+    if funds.len() != 1 {
+        return Err(ContractError::InvalidDepositFunds { count: funds.len() });
+    }
+    if funds[0].denom != token {
+        return Err(ContractError::InvalidDepositDenom {
+            expected: token.to_string(),
+            received: funds[0].denom.to_string(),
+        });
+    }
+    if funds[0].amount != amount {
+        return Err(ContractError::InvalidDepositAmount {
+            expected: amount,
+            received: funds[0].amount,
+        });
+    }
+    Ok(())
+}
+
+/// increase_available_liquidity adds deposited tokens to the pool liquidity
+pub fn increase_available_liquidity(
+    storage: &mut dyn Storage,
+    token: &str,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let available = read_available_liquidity(storage, token).checked_add(amount)?;
+    AVAILABLE_LIQUIDITIES.save(storage, token, &available)?;
+    Ok(available)
+}
+
+/// decrease_available_liquidity reserves pool liquidity for an outgoing transfer
+pub fn decrease_available_liquidity(
+    storage: &mut dyn Storage,
+    token: &str,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let available = read_available_liquidity(storage, token);
+    if available < amount {
+        return Err(ContractError::InsufficientLiquidity {
+            token: token.to_string(),
+            available,
+            requested: amount,
+        });
+    }
+    let available = available - amount;
+    AVAILABLE_LIQUIDITIES.save(storage, token, &available)?;
+    Ok(available)
+}
+
+/// increase_bridged_out records a bridge withdrawal in the pool totals
+fn increase_bridged_out(
+    storage: &mut dyn Storage,
+    token: &str,
+    amount: Uint128,
+    height: u64,
+) -> Result<(), ContractError> {
+    let mut pool = POOLS.may_load(storage, token)?.unwrap_or_default();
+    pool.total_bridged_out = pool.total_bridged_out.checked_add(amount)?;
+    POOLS.save(storage, token, &pool, height)?;
+    Ok(())
+}
+
+pub fn read_available_liquidity(storage: &dyn Storage, token: &str) -> Uint128 {
+    AVAILABLE_LIQUIDITIES
+        .may_load(storage, token)
+        .unwrap_or_default()
+        .unwrap_or_default()
+}
+
+/// get_signers verifies the signatures of a withdraw message hash against the registered
+/// signers and returns the distinct signers.
+/// Every key type signs the same 32 bytes message hash.
+pub fn get_signers(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    env: &Env,
+    message_hash: &[u8],
+    signatures: &[WithdrawSignature],
+) -> Result<Vec<String>, ContractError> {
+    let mut signers: Vec<String> = vec![];
+    for signature in signatures.iter() {
+        let signer = verify_signature(storage, api, env, message_hash, signature)?;
+        if !signers.contains(&signer) {
+            signers.push(signer);
+        }
+    }
+    Ok(signers)
+}
+
+/// verify_signature dispatches the signature verification on the key type of the signer
+/// and returns the registered signer, if it is active at the current block height
+pub fn verify_signature(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    env: &Env,
+    message_hash: &[u8],
+    signature: &WithdrawSignature,
+) -> Result<String, ContractError> {
+    let signer = match &signature.signer {
+        Some(signer) => signer_key(signer),
+        None => recover_signer(api, message_hash, &signature.signature)?,
+    };
+    let registered = SIGNERS
+        .may_load(storage, signer.as_str())?
+        .ok_or(ContractError::InvalidSigner {})?;
+    match registered.status(env.block.height) {
+        SignerStatus::Active | SignerStatus::Retiring => {}
+        SignerStatus::Pending | SignerStatus::Retired => {
+            return Err(ContractError::InactiveSigner {})
+        }
+    }
+
+    let verified = match registered.key_type {
+        KeyType::EthSecp256k1 => {
+            recover_signer(api, message_hash, &signature.signature)? == registered.signer
+        }
+        KeyType::CosmosSecp256k1 => api
+            .secp256k1_verify(
+                message_hash,
+                &decode_hex(&signature.signature)?,
+                &decode_hex(&registered.signer)?,
+            )
+            .map_err(|_| ContractError::InvalidSignature {})?,
+        KeyType::Ed25519 => api
+            .ed25519_verify(
+                message_hash,
+                &decode_hex(&signature.signature)?,
+                &decode_hex(&registered.signer)?,
+            )
+            .map_err(|_| ContractError::InvalidSignature {})?,
+    };
+    if !verified {
+        return Err(ContractError::InvalidSigner {});
+    }
+    Ok(registered.signer)
+}
+
+/// recover_signer gets the Ethereum-style signer address from a message hash and signature
+pub fn recover_signer(
+    api: &dyn Api,
+    message_hash: &[u8],
+    signature: &str,
+) -> Result<String, ContractError> {
+    let signature = decode_hex(signature)?;
+    if signature.len() != 65 {
+        return Err(ContractError::InvalidSignature {});
+    }
+    // Transform yellow paper V from 27/28 to 0/1
+    let recovery_param = match signature[64] {
+        0 | 1 => signature[64],
+        27 | 28 => signature[64] - 27,
+        _ => return Err(ContractError::InvalidSignature {}),
+    };
+    let pubkey = api
+        .secp256k1_recover_pubkey(message_hash, &signature[..64], recovery_param)
+        .map_err(|_| ContractError::InvalidSignature {})?;
+
+    Ok(pubkey_to_address(&pubkey))
+}
+
+/// Validates a signer key and returns the form it is stored under: lower case
+/// `0x` prefixed address for Ethereum signers, lower case hex public key otherwise
+pub fn normalize_signer_key(signer: &str, key_type: &KeyType) -> Result<String, ContractError> {
+    let signer = signer.to_lowercase();
+    let key = hex::decode(signer.strip_prefix("0x").unwrap_or(&signer))
+        .map_err(|_| ContractError::InvalidSignerKey {})?;
+    let expected_len = match key_type {
+        KeyType::EthSecp256k1 => 20,
+        KeyType::CosmosSecp256k1 => 33,
+        KeyType::Ed25519 => 32,
+    };
+    if key.len() != expected_len {
+        return Err(ContractError::InvalidSignerKey {});
+    }
+    match key_type {
+        KeyType::EthSecp256k1 => Ok(format!("0x{}", hex::encode(key))),
+        _ => Ok(hex::encode(key)),
+    }
+}
+
+/// Returns the key a signer is stored under in `SIGNERS`
+pub fn signer_key(signer: &str) -> String {
+    let signer = signer.to_lowercase();
+    let key = signer.strip_prefix("0x").unwrap_or(&signer);
+    // Ethereum addresses are 20 bytes and keep their 0x prefix
+    if key.len() == 40 {
+        return format!("0x{}", key);
+    }
+    key.to_string()
+}
+
+pub fn fee_distribution_name(fee_distribution: &FeeDistribution) -> &'static str {
+    match fee_distribution {
+        FeeDistribution::Compound => "compound",
+        FeeDistribution::RewardIndex => "reward_index",
+    }
+}
+
+pub fn key_type_name(key_type: &KeyType) -> &'static str {
+    match key_type {
+        KeyType::EthSecp256k1 => "eth_secp256k1",
+        KeyType::CosmosSecp256k1 => "cosmos_secp256k1",
+        KeyType::Ed25519 => "ed25519",
+    }
+}
+
+/// get_message_hash calculates the hash signed for a withdraw sign message
+pub fn get_message_hash(
+    sign_mode: &SignMode,
+    env: &Env,
+    message: &WithdrawSignMessage,
+) -> StdResult<Vec<u8>> {
+    match sign_mode {
+        SignMode::Eip191 => {
+            let message_bytes = to_vec(message)?;
+            Ok(eth_message_hash(&message_bytes))
+        }
+        SignMode::Eip712 => {
+            let domain_separator = eip712_domain_separator(
+                CONTRACT_NAME,
+                CONTRACT_VERSION,
+                &env.block.chain_id,
+                env.contract.address.as_str(),
+            );
+            Ok(eip712_withdraw_hash(&domain_separator, message))
+        }
+    }
+}
+
+/// Derives the Ethereum address from an uncompressed secp256k1 public key
+pub fn pubkey_to_address(pubkey: &[u8]) -> String {
+    let hash = keccak256(&pubkey[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+fn decode_hex(data: &str) -> Result<Vec<u8>, ContractError> {
+    let data = data.strip_prefix("0x").unwrap_or(data);
+    hex::decode(data).map_err(|_| ContractError::InvalidSignature {})
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if let Some(call) = WITHDRAW_CALLS.may_load(deps.storage, msg.id)? {
+        return reply_withdraw_call(deps, msg, call);
+    }
+    if let Some(token) = LP_TOKEN_INSTANTIATES.may_load(deps.storage, msg.id)? {
+        return reply_lp_token_instantiate(deps, msg, token);
+    }
+    Err(ContractError::UnknownReplyId { id: msg.id })
+}
+
+pub fn reply_lp_token_instantiate(
+    deps: DepsMut,
+    msg: Reply,
+    token: String,
+) -> Result<Response, ContractError> {
+    LP_TOKEN_INSTANTIATES.remove(deps.storage, msg.id);
+
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let lp_token = deps.api.addr_validate(&res.contract_address)?;
+    LP_TOKENS.save(deps.storage, &token, &lp_token)?;
+    LP_TOKEN_ASSETS.save(deps.storage, &lp_token, &token)?;
+
+    let mut rsp = Response::default();
+    let event = RegisterLpTokenEvent {
+        token: token.as_str(),
+        lp_token: lp_token.as_str(),
+    };
+    event.add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn reply_withdraw_call(
+    deps: DepsMut,
+    msg: Reply,
+    call: WithdrawCall,
+) -> Result<Response, ContractError> {
+    WITHDRAW_CALLS.remove(deps.storage, msg.id);
+
+    let mut rsp = Response::default();
+    if let SubMsgResult::Err(error) = msg.result {
+        // the failed call reverted the transfer, the funds are still held by the pool
+        let payee = Addr::unchecked(&call.payee);
+        for coin in call.amount {
+            let asset_info = load_asset_info(deps.storage, &coin.denom)?;
+            rsp = rsp.add_message(asset_transfer(&asset_info, &payee, coin.amount)?);
+        }
+        let event = WithdrawCallFallbackEvent {
+            payee: call.payee.as_str(),
+            error: error.as_str(),
+        };
+        event.add_attributes(&mut rsp);
+    }
+    Ok(rsp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: MultiswapQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MultiswapQueryMsg::Liquidity { owner, token } => {
+            to_binary(&query_liquidity(deps, owner, token)?)
+        }
+        MultiswapQueryMsg::AllLiquidity {} => to_binary(&query_all_liquidity(deps)?),
+        MultiswapQueryMsg::Owner {} => to_binary(&query_owner(deps)?),
+        MultiswapQueryMsg::Signers { start_after, limit } => {
+            to_binary(&query_signers(deps, env, start_after, limit)?)
+        }
+        MultiswapQueryMsg::FoundryAssets { start_after, limit } => {
+            to_binary(&query_foundry_assets(deps, start_after, limit)?)
+        }
+        MultiswapQueryMsg::FoundryAsset { token } => to_binary(&query_foundry_asset(deps, token)?),
+        MultiswapQueryMsg::UnbondingPeriod { token } => {
+            to_binary(&query_unbonding_period(deps, token)?)
+        }
+        MultiswapQueryMsg::Pool { token } => to_binary(&query_pool(deps, token)?),
+        MultiswapQueryMsg::LiquidityAtHeight {
+            owner,
+            token,
+            height,
+        } => to_binary(&query_liquidity_at_height(deps, owner, token, height)?),
+        MultiswapQueryMsg::PoolTotalAtHeight { token, height } => {
+            to_binary(&query_pool_total_at_height(deps, token, height)?)
+        }
+        MultiswapQueryMsg::Pools { start_after, limit } => {
+            to_binary(&query_pools(deps, start_after, limit)?)
+        }
+        MultiswapQueryMsg::LiquidityProviders { start_after, limit } => {
+            to_binary(&query_liquidity_providers(deps, start_after, limit)?)
+        }
+        MultiswapQueryMsg::PendingRewards { owner, token } => {
+            to_binary(&query_pending_rewards(deps, owner, token)?)
+        }
+        MultiswapQueryMsg::Claims { owner } => to_binary(&query_claims(deps, owner)?),
+        MultiswapQueryMsg::Operators { owner } => to_binary(&query_operators(deps, env, owner)?),
+        MultiswapQueryMsg::LpToken { token } => to_binary(&query_lp_token(deps, token)?),
+        MultiswapQueryMsg::Config {} => to_binary(&query_config(deps)?),
+        MultiswapQueryMsg::UsedMessage { hash } => to_binary(&query_used_message(deps, hash)?),
+        MultiswapQueryMsg::IsWithdrawProcessed {
+            payee,
+            asset_info,
+            amount,
+            salt,
+            expires_at,
+            msg,
+        } => to_binary(&query_is_withdraw_processed(
+            deps,
+            env,
+            WithdrawSignedItem {
+                payee,
+                asset_info,
+                amount,
+                salt,
+                signatures: vec![],
+                expires_at,
+                msg,
+            },
+        )?),
+        MultiswapQueryMsg::VerifyWithdraw {
+            payee,
+            asset_info,
+            amount,
+            salt,
+            signatures,
+            expires_at,
+            msg,
+        } => to_binary(&query_verify_withdraw(
+            deps,
+            env,
+            WithdrawSignedItem {
+                payee,
+                asset_info,
+                amount,
+                salt,
+                signatures,
+                expires_at,
+                msg,
+            },
+        )?),
+    }
+}
+
+pub fn query_owner(deps: Deps) -> StdResult<String> {
+    let owner = OWNER.load(deps.storage)?;
+    return Ok(owner.to_string());
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Config> {
+    CONFIG.load(deps.storage)
+}
+
+pub fn query_liquidity(deps: Deps, owner: String, token: String) -> StdResult<Liquidity> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    if let Ok(Some(position)) = LIQUIDITIES.may_load(deps.storage, (&token, &owner_addr)) {
+        return to_liquidity(deps.storage, owner_addr.to_string(), token, position.shares);
+    }
+    return Err(StdError::generic_err("liquidity does not exist"));
+}
+
+/// to_liquidity values liquidity shares at the current pool share price
+fn to_liquidity(
+    storage: &dyn Storage,
+    user: String,
+    token: String,
+    shares: Uint128,
+) -> StdResult<Liquidity> {
+    let pool = POOLS.may_load(storage, &token)?.unwrap_or_default();
+    let amount = if pool.total_shares.is_zero() {
+        Uint128::zero()
+    } else {
+        shares.multiply_ratio(pool.total_liquidity, pool.total_shares)
+    };
+    Ok(Liquidity {
+        user,
+        token,
+        shares,
+        amount,
     })
 }
 
-pub fn read_signers(
+pub fn query_all_liquidity(deps: Deps) -> StdResult<Vec<Liquidity>> {
+    // Ok(LIQUIDITIES.may_load(deps.storage)?.unwrap_or_default())
+    // Err(StdError::generic_err("not implemented yet"))
+    // let limit: u32 = 30;
+    return read_liquidities(deps.storage, deps.api);
+}
+
+pub fn query_signers(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SignerInfo>> {
+    let signers = read_signers(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|signer| SignerInfo {
+            status: signer.status(env.block.height),
+            signer: signer.signer,
+            key_type: signer.key_type,
+            active_from: signer.active_from,
+            valid_until: signer.valid_until,
+        })
+        .collect();
+    Ok(signers)
+}
+
+pub fn query_foundry_assets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FoundryAsset>> {
+    read_foundry_assets(deps.storage, start_after, limit)
+}
+
+pub fn query_foundry_asset(deps: Deps, token: String) -> StdResult<FoundryAsset> {
+    FOUNDRY_ASSETS.load(deps.storage, &token)
+}
+
+pub fn query_pool(deps: Deps, token: String) -> StdResult<PoolInfo> {
+    let pool = POOLS.may_load(deps.storage, &token)?.unwrap_or_default();
+    Ok(to_pool_info(deps.storage, token, pool))
+}
+
+pub fn query_pools(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PoolInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    POOLS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (token, pool) = item?;
+            Ok(to_pool_info(deps.storage, token, pool))
+        })
+        .collect::<StdResult<Vec<PoolInfo>>>()
+}
+
+/// query_liquidity_at_height values the position at the pool share price of the same height.
+/// Positions of tokens with an LP receipt token are held as cw20 balances without history,
+/// so those tokens are rejected rather than reported as empty.
+pub fn query_liquidity_at_height(
+    deps: Deps,
+    owner: String,
+    token: String,
+    height: u64,
+) -> StdResult<Liquidity> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    if LP_TOKENS.has(deps.storage, &token) {
+        return Err(StdError::generic_err(
+            "liquidity history is not recorded for LP receipt tokens",
+        ));
+    }
+    let shares = LIQUIDITIES
+        .may_load_at_height(deps.storage, (&token, &owner_addr), height)?
+        .map(|position| position.shares)
+        .unwrap_or_default();
+    let pool = POOLS
+        .may_load_at_height(deps.storage, &token, height)?
+        .unwrap_or_default();
+    let amount = if pool.total_shares.is_zero() {
+        Uint128::zero()
+    } else {
+        shares.multiply_ratio(pool.total_liquidity, pool.total_shares)
+    };
+    Ok(Liquidity {
+        user: owner_addr.to_string(),
+        token,
+        shares,
+        amount,
+    })
+}
+
+pub fn query_pool_total_at_height(deps: Deps, token: String, height: u64) -> StdResult<PoolTotal> {
+    let pool = POOLS
+        .may_load_at_height(deps.storage, &token, height)?
+        .unwrap_or_default();
+    Ok(PoolTotal {
+        token,
+        height,
+        total_shares: pool.total_shares,
+        total_liquidity: pool.total_liquidity,
+    })
+}
+
+pub fn query_liquidity_providers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    LIQUIDITY_PROVIDERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|provider| provider.to_string()))
+        .collect::<StdResult<Vec<String>>>()
+}
+
+fn to_pool_info(storage: &dyn Storage, token: String, pool: LiquidityPool) -> PoolInfo {
+    PoolInfo {
+        available_liquidity: read_available_liquidity(storage, &token),
+        token,
+        total_shares: pool.total_shares,
+        total_liquidity: pool.total_liquidity,
+        total_bridged_in: pool.total_bridged_in,
+        total_bridged_out: pool.total_bridged_out,
+        provider_count: pool.provider_count,
+    }
+}
+
+pub fn query_pending_rewards(deps: Deps, owner: String, token: String) -> StdResult<Uint128> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let pool = POOLS.may_load(deps.storage, &token)?.unwrap_or_default();
+    let mut position = LIQUIDITIES
+        .may_load(deps.storage, (&token, &owner_addr))?
+        .unwrap_or_default();
+    settle_rewards(&pool, &mut position)?;
+    Ok(position.pending_rewards)
+}
+
+pub fn query_unbonding_period(deps: Deps, token: String) -> StdResult<Option<Duration>> {
+    UNBONDING_PERIODS.may_load(deps.storage, &token)
+}
+
+pub fn query_claims(deps: Deps, owner: String) -> StdResult<Vec<Claim>> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    Ok(CLAIMS
+        .may_load(deps.storage, &owner_addr)?
+        .unwrap_or_default())
+}
+
+pub fn query_operators(deps: Deps, env: Env, owner: String) -> StdResult<Vec<Approval>> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let approvals = OPERATORS
+        .prefix(&owner_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(approvals
+        .into_iter()
+        .filter(|(_, expires)| !expires.is_expired(&env.block))
+        .map(|(operator, expires)| Approval {
+            operator: operator.to_string(),
+            expires,
+        })
+        .collect())
+}
+
+pub fn query_lp_token(deps: Deps, token: String) -> StdResult<Option<String>> {
+    let lp_token = LP_TOKENS.may_load(deps.storage, &token)?;
+    Ok(lp_token.map(|addr| addr.to_string()))
+}
+
+pub fn query_used_message(deps: Deps, hash: String) -> StdResult<bool> {
+    let hash = hex::decode(hash.strip_prefix("0x").unwrap_or(&hash))
+        .map_err(|_| StdError::generic_err("invalid message hash"))?;
+    Ok(is_used_message(deps.storage, &hash))
+}
+
+pub fn query_is_withdraw_processed(
+    deps: Deps,
+    env: Env,
+    item: WithdrawSignedItem,
+) -> StdResult<bool> {
+    let config = CONFIG.load(deps.storage)?;
+    let message = withdraw_sign_message(&env, &item);
+    let message_hash = get_message_hash(&config.sign_mode, &env, &message)?;
+    Ok(is_used_message(deps.storage, &message_hash))
+}
+
+pub fn query_verify_withdraw(
+    deps: Deps,
+    env: Env,
+    item: WithdrawSignedItem,
+) -> StdResult<VerifyWithdrawResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let message = withdraw_sign_message(&env, &item);
+    let message_hash = get_message_hash(&config.sign_mode, &env, &message)?;
+
+    let signatures = item
+        .signatures
+        .iter()
+        .map(|signature| {
+            let signer = match &signature.signer {
+                Some(signer) => Some(signer_key(signer)),
+                None => recover_signer(deps.api, &message_hash, &signature.signature).ok(),
+            };
+            let registered = signer
+                .as_ref()
+                .map(|signer| is_signer(deps.storage, signer.to_string()))
+                .unwrap_or(false);
+            let error = verify_signature(deps.storage, deps.api, &env, &message_hash, signature)
+                .err()
+                .map(|err| err.to_string());
+            SignatureVerification {
+                signer,
+                registered,
+                error,
+            }
+        })
+        .collect();
+
+    let available = read_available_liquidity(deps.storage, item.asset_info.token());
+    let error = verify_withdraw_signed(deps.storage, deps.api, &env, &config, &item)
+        .err()
+        .map(|err| err.to_string());
+
+    Ok(VerifyWithdrawResponse {
+        message_hash: hex::encode(&message_hash),
+        signatures,
+        used: is_used_message(deps.storage, &message_hash),
+        sufficient_liquidity: available >= item.amount,
+        error,
+    })
+}
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+pub fn read_liquidities(
     storage: &dyn Storage,
     api: &dyn Api,
     // start_after: Option<(String, String)>,
     // limit: Option<u32>,
-) -> Vec<String> {
+) -> StdResult<Vec<Liquidity>> {
     let limit = DEFAULT_LIMIT as usize;
     // let start = calc_range_start(start_after);
     // let start_key = start.map(Bound::exclusive);
 
-    return SIGNERS
+    LIQUIDITIES
         .range(storage, None, None, Order::Ascending)
         .take(limit)
         .map(|item| {
-            if let Ok((_, it)) = item {
-                return it;
-            }
-            return "".to_string();
+            let ((token, user), position) = item?;
+            to_liquidity(storage, user.to_string(), token, position.shares)?.to_normal(api)
         })
-        .collect::<Vec<String>>();
+        .collect::<StdResult<Vec<Liquidity>>>()
+}
+
+// this will set the first key after the provided key, by appending a 1 byte
+fn calc_range_start(start_after: Option<(String, String)>) -> Option<Vec<u8>> {
+    start_after.map(|info| {
+        let mut v = [info.0.as_bytes(), info.1.as_bytes()]
+            .concat()
+            .as_slice()
+            .to_vec();
+        v.push(1);
+        v
+    })
+}
+
+pub fn read_signers(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Signer>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|signer| signer_key(&signer));
+    let start = start.as_deref().map(Bound::exclusive);
+
+    SIGNERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, signer)| signer))
+        .collect::<StdResult<Vec<Signer>>>()
 }
 
 pub fn is_signer(storage: &dyn Storage, signer: String) -> bool {
@@ -518,24 +2340,33 @@ pub fn is_signer(storage: &dyn Storage, signer: String) -> bool {
 
 pub fn read_foundry_assets(
     storage: &dyn Storage,
-    api: &dyn Api,
-    // start_after: Option<(String, String)>,
-    // limit: Option<u32>,
-) -> Vec<String> {
-    let limit = DEFAULT_LIMIT as usize;
-    // let start = calc_range_start(start_after);
-    // let start_key = start.map(Bound::exclusive);
-
-    return FOUNDRY_ASSETS
-        .range(storage, None, None, Order::Ascending)
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FoundryAsset>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    FOUNDRY_ASSETS
+        .range(storage, start, None, Order::Ascending)
         .take(limit)
-        .map(|item| {
-            if let Ok((_, it)) = item {
-                return it;
-            }
-            return "".to_string();
-        })
-        .collect::<Vec<String>>();
+        .map(|item| item.map(|(_, asset)| asset))
+        .collect::<StdResult<Vec<FoundryAsset>>>()
+}
+
+/// load_active_foundry_asset loads a foundry asset that accepts liquidity, swaps and withdrawals
+fn load_active_foundry_asset(
+    storage: &dyn Storage,
+    token: &str,
+) -> Result<FoundryAsset, ContractError> {
+    let asset = FOUNDRY_ASSETS
+        .may_load(storage, token)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if asset.status != AssetStatus::Active {
+        return Err(ContractError::AssetWithdrawOnly {
+            token: token.to_string(),
+        });
+    }
+    Ok(asset)
 }
 
 pub fn is_foundry_asset(storage: &dyn Storage, foundry_asset: String) -> bool {
@@ -545,7 +2376,475 @@ pub fn is_foundry_asset(storage: &dyn Storage, foundry_asset: String) -> bool {
     return false;
 }
 
+pub fn is_used_message(storage: &dyn Storage, message_hash: &[u8]) -> bool {
+    if let Ok(Some(_)) = USED_MESSAGES.may_load(storage, message_hash) {
+        return true;
+    }
+    return false;
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // contracts deployed before the threshold was introduced require a single signer
+    if CONFIG.may_load(deps.storage)?.is_none() {
+        let config = Config {
+            threshold: DEFAULT_THRESHOLD,
+            sign_mode: SignMode::Eip191,
+            swap_fee_bps: 0,
+            fee_distribution: FeeDistribution::Compound,
+            lp_token_code_id: None,
+            provider_allowlist: false,
+        };
+        CONFIG.save(deps.storage, &config)?;
+    }
+    if let Some(code_id) = msg.lp_token_code_id {
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.lp_token_code_id = Some(code_id);
+            Ok(config)
+        })?;
+    }
+    if let Some(sign_mode) = msg.sign_mode {
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.sign_mode = sign_mode;
+            Ok(config)
+        })?;
+    }
+    let mut rsp = Response::default();
+    // liquidity provided before share accounting is converted to shares one to one. The
+    // legacy amounts were never checked against deposits, so a pool is credited with no
+    // more than the contract holds and the shares are worth their pro rata part of it.
+    let legacy = LEGACY_LIQUIDITIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut legacy_totals: BTreeMap<String, Uint128> = BTreeMap::new();
+    for ((token, user), liquidity) in legacy {
+        LEGACY_LIQUIDITIES.remove(deps.storage, (&token, &user));
+        if liquidity.amount.is_zero() {
+            continue;
+        }
+        save_liquidity_shares(
+            deps.storage,
+            &token,
+            &user,
+            liquidity.amount,
+            env.block.height,
+        )?;
+        let total = legacy_totals.entry(token).or_default();
+        *total = total.checked_add(liquidity.amount)?;
+    }
+    for (token, amount) in legacy_totals {
+        let mut pool = POOLS.may_load(deps.storage, &token)?.unwrap_or_default();
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), token.to_string())?;
+        let credited = amount.min(balance.amount.saturating_sub(pool.total_liquidity));
+        pool.total_shares = pool.total_shares.checked_add(amount)?;
+        pool.total_liquidity = pool.total_liquidity.checked_add(credited)?;
+        POOLS.save(deps.storage, &token, &pool, env.block.height)?;
+        rsp = rsp.add_attribute("migrated_liquidity", format!("{}{}", credited, token));
+    }
+    // foundry assets listed before asset records are kept without limits
+    let legacy = LEGACY_FOUNDRY_ASSETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token, _) in legacy {
+        LEGACY_FOUNDRY_ASSETS.remove(deps.storage, &token);
+        let asset = FoundryAsset {
+            token: token.to_string(),
+            symbol: None,
+            decimals: None,
+            added_at: None,
+            max_total_liquidity: None,
+            max_per_provider: None,
+            provider_allowlist: false,
+            status: AssetStatus::Active,
+            cw20: false,
+        };
+        FOUNDRY_ASSETS.save(deps.storage, &token, &asset)?;
+    }
+    // signers registered before key types are Ethereum addresses, re-keyed to lower case
+    // so they match recovered addresses. Other keys could never match a recovered signer
+    // and are dropped, each reported as a `skipped_signer` attribute.
+    let legacy = LEGACY_SIGNERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, signer) in legacy {
+        LEGACY_SIGNERS.remove(deps.storage, &key);
+        let key = match normalize_signer_key(&signer, &KeyType::EthSecp256k1) {
+            Ok(key) => key,
+            Err(_) => {
+                rsp = rsp.add_attribute("skipped_signer", signer);
+                continue;
+            }
+        };
+        let signer = Signer {
+            signer: key.to_string(),
+            key_type: KeyType::EthSecp256k1,
+            active_from: None,
+            valid_until: None,
+        };
+        SIGNERS.save(deps.storage, &key, &signer)?;
+    }
+    // contracts deployed before liquidity tracking start from their current balances
+    let tokens = FOUNDRY_ASSETS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for token in tokens {
+        if AVAILABLE_LIQUIDITIES.has(deps.storage, &token) {
+            continue;
+        }
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), token.to_string())?;
+        AVAILABLE_LIQUIDITIES.save(deps.storage, &token, &balance.amount)?;
+    }
+    Ok(rsp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::OwnedDeps;
+    use k256::ecdsa::signature::Signer as _;
+    use k256::ecdsa::{recoverable, SigningKey};
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    use multiswap::eth_message_hash;
+
+    const OWNER_ADDR: &str = "owner";
+    const PROVIDER: &str = "provider";
+    const RELAYER: &str = "relayer";
+    const PAYEE: &str = "payee";
+    const DENOM: &str = "stake";
+
+    fn signing_key(secret: u8) -> SigningKey {
+        let mut bytes = [0u8; 32];
+        bytes[31] = secret;
+        SigningKey::from_bytes(&bytes).unwrap()
+    }
+
+    fn eth_address(key: &SigningKey) -> String {
+        let pubkey = key.verifying_key().to_encoded_point(false);
+        pubkey_to_address(pubkey.as_bytes())
+    }
+
+    /// Signs a message like `personal_sign`, with V as 27 or 28
+    fn personal_sign(key: &SigningKey, message: &[u8]) -> Vec<u8> {
+        let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        prefixed.extend_from_slice(message);
+        let signature: recoverable::Signature = key.sign(&prefixed);
+        let mut signature = signature.as_ref().to_vec();
+        signature[64] += 27;
+        signature
+    }
+
+    fn native_asset() -> AssetInfo {
+        AssetInfo::Native {
+            denom: DENOM.to_string(),
+        }
+    }
+
+    fn setup(
+        threshold: u32,
+        signers: &[&SigningKey],
+    ) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: OWNER_ADDR.to_string(),
+            threshold: Some(threshold),
+            sign_mode: None,
+            swap_fee_bps: Some(100),
+            fee_distribution: None,
+            lp_token_code_id: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info(OWNER_ADDR, &[]), msg).unwrap();
+        let msg = MultiswapExecuteMsg::AddFoundryAsset {
+            token: DENOM.to_string(),
+            asset_info: None,
+            symbol: None,
+            decimals: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER_ADDR, &[]), msg).unwrap();
+        for key in signers {
+            let msg = MultiswapExecuteMsg::AddSigner {
+                signer: eth_address(key),
+                key_type: None,
+                active_from: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(OWNER_ADDR, &[]), msg).unwrap();
+        }
+        deps
+    }
+
+    fn add_liquidity(deps: DepsMut, sender: &str, amount: u128) -> Result<Response, ContractError> {
+        let msg = MultiswapExecuteMsg::AddLiquidity {
+            asset_info: native_asset(),
+            amount: Uint128::new(amount),
+            owner: None,
+        };
+        execute(
+            deps,
+            mock_env(),
+            mock_info(sender, &coins(amount, DENOM)),
+            msg,
+        )
+    }
+
+    fn swap(deps: DepsMut, amount: u128) -> Result<Response, ContractError> {
+        let msg = MultiswapExecuteMsg::Swap {
+            asset_info: native_asset(),
+            amount: Uint128::new(amount),
+            target_chain_id: "1".to_string(),
+            target_token: "0x00".to_string(),
+            target_address: "0x00".to_string(),
+        };
+        execute(
+            deps,
+            mock_env(),
+            mock_info("swapper", &coins(amount, DENOM)),
+            msg,
+        )
+    }
+
+    fn signed_item(keys: &[&SigningKey], salt: &str, expires_at: Expiration) -> WithdrawSignedItem {
+        let mut item = WithdrawSignedItem {
+            payee: PAYEE.to_string(),
+            asset_info: native_asset(),
+            amount: Uint128::new(1_000),
+            salt: salt.to_string(),
+            signatures: vec![],
+            expires_at,
+            msg: None,
+        };
+        let message = to_vec(&withdraw_sign_message(&mock_env(), &item)).unwrap();
+        item.signatures = keys
+            .iter()
+            .map(|key| WithdrawSignature {
+                signer: None,
+                signature: format!("0x{}", hex::encode(personal_sign(key, &message))),
+            })
+            .collect();
+        item
+    }
+
+    fn withdraw_signed(deps: DepsMut, item: WithdrawSignedItem) -> Result<Response, ContractError> {
+        let msg = MultiswapExecuteMsg::WithdrawSigned {
+            payee: item.payee,
+            salt: item.salt,
+            asset_info: item.asset_info,
+            amount: item.amount,
+            signatures: item.signatures,
+            expires_at: item.expires_at,
+            msg: item.msg,
+        };
+        execute(deps, mock_env(), mock_info(RELAYER, &[]), msg)
+    }
+
+    #[test]
+    fn recover_signer_eip191() {
+        let api = MockApi::default();
+        let key = signing_key(1);
+        // well known address of the private key 1
+        let address = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+        assert_eq!(eth_address(&key), address);
+
+        let mut signature = personal_sign(&key, b"hello world");
+        let message_hash = eth_message_hash(b"hello world");
+        assert!(signature[64] == 27 || signature[64] == 28);
+        let recovered = recover_signer(&api, &message_hash, &hex::encode(&signature)).unwrap();
+        assert_eq!(recovered, address);
+
+        // V as recovery id 0 or 1, with and without 0x prefix
+        signature[64] -= 27;
+        let recovered = recover_signer(
+            &api,
+            &message_hash,
+            &format!("0x{}", hex::encode(&signature)),
+        )
+        .unwrap();
+        assert_eq!(recovered, address);
+
+        signature[64] = 29;
+        let err = recover_signer(&api, &message_hash, &hex::encode(&signature)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+        let err = recover_signer(&api, &message_hash, &hex::encode(&signature[..64])).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+    }
+
+    #[test]
+    fn withdraw_signed_rejects_unregistered_signer() {
+        let signer = signing_key(1);
+        let mut deps = setup(1, &[&signer]);
+        add_liquidity(deps.as_mut(), PROVIDER, 1_000_000).unwrap();
+
+        let item = signed_item(&[&signing_key(2)], "0x01", Expiration::Never {});
+        let err = withdraw_signed(deps.as_mut(), item).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSigner {}));
+
+        let item = signed_item(&[&signer], "0x01", Expiration::Never {});
+        withdraw_signed(deps.as_mut(), item).unwrap();
+        assert_eq!(
+            read_available_liquidity(&deps.storage, DENOM),
+            Uint128::new(999_000)
+        );
+    }
+
+    #[test]
+    fn withdraw_signed_rejects_replay() {
+        let signer = signing_key(1);
+        let mut deps = setup(1, &[&signer]);
+        add_liquidity(deps.as_mut(), PROVIDER, 1_000_000).unwrap();
+
+        let item = signed_item(&[&signer], "0x01", Expiration::Never {});
+        withdraw_signed(deps.as_mut(), item.clone()).unwrap();
+        let err = withdraw_signed(deps.as_mut(), item).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyUsedWithdrawMessage {}));
+
+        // a new salt is a new withdrawal
+        let item = signed_item(&[&signer], "0x02", Expiration::Never {});
+        withdraw_signed(deps.as_mut(), item).unwrap();
+    }
+
+    #[test]
+    fn withdraw_signed_counts_distinct_signers() {
+        let (first, second) = (signing_key(1), signing_key(2));
+        let mut deps = setup(2, &[&first, &second]);
+        add_liquidity(deps.as_mut(), PROVIDER, 1_000_000).unwrap();
+
+        let item = signed_item(&[&first, &first], "0x01", Expiration::Never {});
+        let err = withdraw_signed(deps.as_mut(), item).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientSignatures {}));
+
+        let item = signed_item(&[&first, &second], "0x01", Expiration::Never {});
+        withdraw_signed(deps.as_mut(), item).unwrap();
+    }
+
+    #[test]
+    fn withdraw_signed_rejects_expired_signatures() {
+        let signer = signing_key(1);
+        let mut deps = setup(1, &[&signer]);
+        add_liquidity(deps.as_mut(), PROVIDER, 1_000_000).unwrap();
+        let height = mock_env().block.height;
+
+        let item = signed_item(&[&signer], "0x01", Expiration::AtHeight(height));
+        let err = withdraw_signed(deps.as_mut(), item).unwrap_err();
+        assert!(matches!(err, ContractError::SignatureExpired {}));
+
+        // the expiry is signed, it can not be extended without new signatures
+        let mut item = signed_item(&[&signer], "0x01", Expiration::AtHeight(height));
+        item.expires_at = Expiration::AtHeight(height + 1);
+        let err = withdraw_signed(deps.as_mut(), item).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSigner {}));
+
+        let item = signed_item(&[&signer], "0x01", Expiration::AtHeight(height + 1));
+        withdraw_signed(deps.as_mut(), item).unwrap();
+    }
+
+    #[test]
+    fn add_liquidity_verifies_deposit() {
+        let mut deps = setup(1, &[]);
+        let msg = MultiswapExecuteMsg::AddLiquidity {
+            asset_info: native_asset(),
+            amount: Uint128::new(1_000),
+            owner: None,
+        };
+
+        let info = mock_info(PROVIDER, &coins(1_000, "other"));
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDepositDenom { .. }));
+
+        let info = mock_info(PROVIDER, &coins(999, DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDepositAmount { .. }));
+
+        let funds = [coins(1_000, DENOM), coins(1, "other")].concat();
+        let info = mock_info(PROVIDER, &funds);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidDepositFunds { count: 2 }
+        ));
+
+        // cw20 assets are deposited through the receive hook
+        let msg = MultiswapExecuteMsg::AddLiquidity {
+            asset_info: AssetInfo::Cw20 {
+                contract_addr: DENOM.to_string(),
+            },
+            amount: Uint128::new(1_000),
+            owner: None,
+        };
+        let info = mock_info(PROVIDER, &coins(1_000, DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidAssetInfo {}));
+    }
+
+    #[test]
+    fn add_liquidity_mints_shares_at_pool_price() {
+        let mut deps = setup(1, &[]);
+        add_liquidity(deps.as_mut(), PROVIDER, 1_000_000).unwrap();
+        // the first deposit locks part of its shares
+        let provider = Addr::unchecked(PROVIDER);
+        assert_eq!(
+            read_liquidity_shares(&deps.storage, DENOM, &provider).unwrap(),
+            Uint128::new(999_000)
+        );
+        let pool = POOLS.load(&deps.storage, DENOM).unwrap();
+        assert_eq!(pool.total_shares, Uint128::new(1_000_000));
+        assert_eq!(pool.locked_shares, MINIMUM_LIQUIDITY);
+
+        // the 1% swap fee compounds into the pool and raises the share price
+        swap(deps.as_mut(), 1_000_000).unwrap();
+        let pool = POOLS.load(&deps.storage, DENOM).unwrap();
+        assert_eq!(pool.total_liquidity, Uint128::new(1_010_000));
+
+        add_liquidity(deps.as_mut(), "second", 101_000).unwrap();
+        let second = Addr::unchecked("second");
+        assert_eq!(
+            read_liquidity_shares(&deps.storage, DENOM, &second).unwrap(),
+            Uint128::new(100_000)
+        );
+        let liquidity =
+            query_liquidity(deps.as_ref(), PROVIDER.to_string(), DENOM.to_string()).unwrap();
+        assert_eq!(liquidity.amount, Uint128::new(1_008_990));
+
+        // deposits too small for a single share are rejected
+        let err = add_liquidity(deps.as_mut(), "third", 1).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidLiquidityShares {}));
+    }
+
+    #[test]
+    fn add_liquidity_requires_minimum_first_deposit() {
+        let mut deps = setup(1, &[]);
+        let err = add_liquidity(deps.as_mut(), PROVIDER, 1_000).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidLiquidityShares {}));
+
+        add_liquidity(deps.as_mut(), PROVIDER, 1_001).unwrap();
+        let provider = Addr::unchecked(PROVIDER);
+        assert_eq!(
+            read_liquidity_shares(&deps.storage, DENOM, &provider).unwrap(),
+            Uint128::new(1)
+        );
+    }
+
+    #[test]
+    fn first_deposit_can_not_inflate_share_price() {
+        let mut deps = setup(1, &[]);
+        // the attacker holds a single share and inflates its price with swap fees
+        add_liquidity(deps.as_mut(), "attacker", 1_001).unwrap();
+        swap(deps.as_mut(), 10_000_000).unwrap();
+        let pool = POOLS.load(&deps.storage, DENOM).unwrap();
+        assert_eq!(pool.total_liquidity, Uint128::new(101_001));
+
+        add_liquidity(deps.as_mut(), "victim", 150_000).unwrap();
+        let victim =
+            query_liquidity(deps.as_ref(), "victim".to_string(), DENOM.to_string()).unwrap();
+        assert_eq!(victim.shares, Uint128::new(1_486));
+        // the victim loses less than a share to rounding
+        assert_eq!(victim.amount, Uint128::new(149_974));
+        let attacker =
+            query_liquidity(deps.as_ref(), "attacker".to_string(), DENOM.to_string()).unwrap();
+        assert_eq!(attacker.amount, Uint128::new(100));
+    }
 }
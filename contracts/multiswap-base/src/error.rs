@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +6,105 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("InvalidDeposit")]
-    InvalidDeposit {},
+    #[error("InvalidDepositFunds: expected a single coin, got {count}")]
+    InvalidDepositFunds { count: usize },
+
+    #[error("InvalidDepositDenom: expected {expected}, got {received}")]
+    InvalidDepositDenom { expected: String, received: String },
+
+    #[error("InvalidDepositAmount: expected {expected}, got {received}")]
+    InvalidDepositAmount {
+        expected: Uint128,
+        received: Uint128,
+    },
+
+    #[error("InsufficientLiquidity: {token} available {available}, requested {requested}")]
+    InsufficientLiquidity {
+        token: String,
+        available: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("LiquidityCapExceeded: {token} cap {cap}, requested {requested}")]
+    LiquidityCapExceeded {
+        token: String,
+        cap: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("ProviderLiquidityCapExceeded: {token} cap {cap}, requested {requested}")]
+    ProviderLiquidityCapExceeded {
+        token: String,
+        cap: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("LiquidityProviderNotAllowed")]
+    LiquidityProviderNotAllowed {},
+
+    #[error("AssetWithdrawOnly: {token} only allows liquidity removal")]
+    AssetWithdrawOnly { token: String },
+
+    #[error("AssetHasLiquidity: {token} still has liquidity providers")]
+    AssetHasLiquidity { token: String },
+
+    #[error("AssetHasClaims: {token} still has unbonding liquidity to claim")]
+    AssetHasClaims { token: String },
+
+    #[error("ApprovalExpired")]
+    ApprovalExpired {},
+
+    #[error("InvalidAssetInfo")]
+    InvalidAssetInfo {},
+
+    #[error("InvalidSignature")]
+    InvalidSignature {},
+
+    #[error("InvalidSigner")]
+    InvalidSigner {},
+
+    #[error("InactiveSigner")]
+    InactiveSigner {},
+
+    #[error("InvalidSignerKey")]
+    InvalidSignerKey {},
+
+    #[error("InvalidThreshold")]
+    InvalidThreshold {},
+
+    #[error("InvalidSwapFee")]
+    InvalidSwapFee {},
+
+    #[error("RewardIndexOverflow")]
+    RewardIndexOverflow {},
+
+    #[error("InvalidLiquidityShares")]
+    InvalidLiquidityShares {},
+
+    #[error("NothingToClaim")]
+    NothingToClaim {},
+
+    #[error("LpTokenExists")]
+    LpTokenExists {},
+
+    #[error("LpTokenNotConfigured")]
+    LpTokenNotConfigured {},
+
+    #[error("InsufficientSignatures")]
+    InsufficientSignatures {},
+
+    #[error("SignatureExpired")]
+    SignatureExpired {},
+
+    #[error("AlreadyUsedWithdrawMessage")]
+    AlreadyUsedWithdrawMessage {},
+
+    #[error("UnknownReplyId {id}")]
+    UnknownReplyId { id: u64 },
 }
@@ -1,7 +1,30 @@
+use cw20::{Cw20Coin, MinterResponse};
+use multiswap::{FeeDistribution, SignMode};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub owner: String,
+    /// Number of distinct signers required for a withdrawal, defaults to 1
+    pub threshold: Option<u32>,
+    /// Hashing scheme used for withdraw signatures, defaults to EIP-191
+    pub sign_mode: Option<SignMode>,
+    /// Share of every swap retained by the liquidity pool in basis points, defaults to 0
+    pub swap_fee_bps: Option<u16>,
+    /// How retained swap fees are distributed, defaults to compounding into the pool
+    pub fee_distribution: Option<FeeDistribution>,
+    /// Code id of the cw20 contract instantiated as LP receipt token for every foundry asset.
+    /// LP receipts are not issued if unset.
+    pub lp_token_code_id: Option<u64>,
+}
+
+/// Instantiate message of the cw20-base LP receipt token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
 }
@@ -1,12 +1,111 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
-use multiswap::Liquidity;
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration};
+use multiswap::{Claim, Config, FoundryAsset, Signer};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Withdrawal paid by executing the payee contract, kept until its reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawCall {
+    pub payee: String,
+    pub amount: Vec<Coin>,
+}
+
+/// Liquidity provided to a token pool. Swap fees are added to `total_liquidity`
+/// without minting shares, which raises the value of every share.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LiquidityPool {
+    pub total_shares: Uint128,
+    pub total_liquidity: Uint128,
+    /// Sum of all swap deposits
+    #[serde(default)]
+    pub total_bridged_in: Uint128,
+    /// Sum of all signed withdrawals
+    #[serde(default)]
+    pub total_bridged_out: Uint128,
+    /// Number of owners holding shares in `LIQUIDITIES`
+    #[serde(default)]
+    pub provider_count: u64,
+    /// Swap fee rewards distributed per share held in `LIQUIDITIES`
+    #[serde(default)]
+    pub reward_index: Decimal,
+    /// Shares held in `LIQUIDITIES`, LP receipt tokens do not earn rewards
+    #[serde(default)]
+    pub reward_shares: Uint128,
+    /// Liquidity removed with an unbonding period and not claimed yet
+    #[serde(default)]
+    pub pending_claims: Uint128,
+    /// Shares of the first deposit that are owned by nobody, part of `total_shares`
+    #[serde(default)]
+    pub locked_shares: Uint128,
+}
+
+/// Liquidity amount stored by contracts deployed before share accounting
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyLiquidity {
+    pub user: String,
+    pub token: String,
+    pub amount: Uint128,
+}
+
+/// Shares an owner holds in a token pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LiquidityPosition {
+    pub shares: Uint128,
+    /// Pool reward index the rewards were last settled at
+    pub reward_index: Decimal,
+    /// Rewards settled but not claimed yet
+    pub pending_rewards: Uint128,
+}
 
 /// Store the owner of the contract to add/remove signers
 pub const OWNER: Item<Addr> = Item::new("owner");
-/// Store the liquidities map, `(owner, token) -> liquidity`
-pub const LIQUIDITIES: Map<(&str, &Addr), Liquidity> = Map::new("liquidities");
+/// Store the contract configuration
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Store the liquidity positions map, `(token, owner) -> position`, with a changelog per block
+pub const LIQUIDITIES: SnapshotMap<(&str, &Addr), LiquidityPosition> = SnapshotMap::new(
+    "liquidity_positions",
+    "liquidity_positions__checkpoints",
+    "liquidity_positions__changelog",
+    Strategy::EveryBlock,
+);
+/// Store the liquidity amounts of contracts deployed before share accounting, migrated to shares
+pub const LEGACY_LIQUIDITIES: Map<(&str, &Addr), LegacyLiquidity> = Map::new("liquidities");
+/// Store the liquidity pools, `token -> pool`, with a changelog per block
+pub const POOLS: SnapshotMap<&str, LiquidityPool> = SnapshotMap::new(
+    "pools",
+    "pools__checkpoints",
+    "pools__changelog",
+    Strategy::EveryBlock,
+);
+/// Store the unbonding periods of liquidity removals, `token -> duration`
+pub const UNBONDING_PERIODS: Map<&str, Duration> = Map::new("unbonding_periods");
+/// Store the pending liquidity claims, `owner -> claims`
+pub const CLAIMS: Map<&Addr, Vec<Claim>> = Map::new("claims");
+/// Store the LP receipt tokens, `token -> cw20 contract`
+pub const LP_TOKENS: Map<&str, Addr> = Map::new("lp_tokens");
+/// Store the foundry assets of LP receipt tokens, `cw20 contract -> token`
+pub const LP_TOKEN_ASSETS: Map<&Addr, String> = Map::new("lp_token_assets");
+/// Store pending LP receipt token instantiations, `reply id -> token`
+pub const LP_TOKEN_INSTANTIATES: Map<u64, String> = Map::new("lp_token_instantiates");
+/// Store the bridge liquidity available for withdrawals, `token -> amount`
+pub const AVAILABLE_LIQUIDITIES: Map<&str, Uint128> = Map::new("available_liquidities");
+/// Store the providers allowed to add liquidity while an allowlist is enabled
+pub const LIQUIDITY_PROVIDERS: Map<&Addr, bool> = Map::new("liquidity_providers");
+/// Store operators approved to manage liquidity, `(owner, operator) -> expiration`
+pub const OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("operators");
 /// Store signers.
-pub const SIGNERS: Map<&str, String> = Map::new("signers");
+pub const SIGNERS: Map<&str, Signer> = Map::new("signer_records");
+/// Store signer addresses of contracts deployed before key types, migrated to `SIGNERS`
+pub const LEGACY_SIGNERS: Map<&str, String> = Map::new("signers");
 /// Store foundry assets.
-pub const FOUNDRY_ASSETS: Map<&str, String> = Map::new("foundry_assets");
+pub const FOUNDRY_ASSETS: Map<&str, FoundryAsset> = Map::new("foundry_asset_records");
+/// Store foundry assets of contracts deployed before asset records, migrated to `FOUNDRY_ASSETS`
+pub const LEGACY_FOUNDRY_ASSETS: Map<&str, String> = Map::new("foundry_assets");
+/// Store hashes of withdraw messages that were already processed.
+pub const USED_MESSAGES: Map<&[u8], bool> = Map::new("used_messages");
+/// Store the last submessage reply id.
+pub const REPLY_SEQ: Item<u64> = Item::new("reply_seq");
+/// Store pending withdraw calls, `reply id -> withdraw call`
+pub const WITHDRAW_CALLS: Map<u64, WithdrawCall> = Map::new("withdraw_calls");
@@ -2,6 +2,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Binary, Uint128};
+use cw_utils::Expiration;
+use multiswap::{AssetInfo, WithdrawSignature, WithdrawSignedItem};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -13,7 +15,7 @@ pub enum FiberRouterExecuteMsg {
         pool: String,
     },
     Swap {
-        token: String,
+        asset_info: AssetInfo,
         amount: Uint128,
         target_chain_id: String,
         target_token: String,
@@ -22,9 +24,17 @@ pub enum FiberRouterExecuteMsg {
     WithdrawSigned {
         payee: String,
         salt: String,
-        token: String,
+        asset_info: AssetInfo,
         amount: Uint128,
-        signature: String,
+        signatures: Vec<WithdrawSignature>,
+        #[serde(default)]
+        expires_at: Expiration,
+        msg: Option<Binary>,
+    },
+    WithdrawSignedBatch {
+        items: Vec<WithdrawSignedItem>,
+        #[serde(default)]
+        skip_processed: bool,
     },
 }
 
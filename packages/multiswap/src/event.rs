@@ -1,5 +1,5 @@
-use cosmwasm_std::{attr, Response, Uint128};
-use cw_utils::Event;
+use cosmwasm_std::{attr, Coin, Response, Uint128};
+use cw_utils::{Duration, Event, Expiration};
 
 /// Tracks ownership transfer
 pub struct TransferOwnershipEvent<'a> {
@@ -19,12 +19,19 @@ impl<'a> Event for TransferOwnershipEvent<'a> {
 pub struct AddSignerEvent<'a> {
     pub from: &'a str,
     pub signer: &'a str,
+    pub key_type: &'a str,
+    pub active_from: Option<u64>,
 }
 
 impl<'a> Event for AddSignerEvent<'a> {
     fn add_attributes(&self, rsp: &mut Response) {
         rsp.attributes.push(attr("action", "add_signer"));
         rsp.attributes.push(attr("signer", self.signer));
+        rsp.attributes.push(attr("key_type", self.key_type));
+        if let Some(active_from) = self.active_from {
+            rsp.attributes
+                .push(attr("active_from", active_from.to_string()));
+        }
         rsp.attributes.push(attr("from", self.from));
     }
 }
@@ -33,12 +40,52 @@ impl<'a> Event for AddSignerEvent<'a> {
 pub struct RemoveSignerEvent<'a> {
     pub from: &'a str,
     pub signer: &'a str,
+    pub valid_until: Option<u64>,
 }
 
 impl<'a> Event for RemoveSignerEvent<'a> {
     fn add_attributes(&self, rsp: &mut Response) {
         rsp.attributes.push(attr("action", "remove_signer"));
         rsp.attributes.push(attr("signer", self.signer));
+        if let Some(valid_until) = self.valid_until {
+            rsp.attributes
+                .push(attr("valid_until", valid_until.to_string()));
+        }
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks signature threshold updates
+pub struct SetThresholdEvent<'a> {
+    pub from: &'a str,
+    pub threshold: u32,
+}
+
+impl<'a> Event for SetThresholdEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "set_threshold"));
+        rsp.attributes
+            .push(attr("threshold", self.threshold.to_string()));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks swap fee updates
+pub struct SetSwapFeeEvent<'a> {
+    pub from: &'a str,
+    pub swap_fee_bps: u16,
+    pub fee_distribution: Option<&'a str>,
+}
+
+impl<'a> Event for SetSwapFeeEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "set_swap_fee"));
+        rsp.attributes
+            .push(attr("swap_fee_bps", self.swap_fee_bps.to_string()));
+        if let Some(fee_distribution) = self.fee_distribution {
+            rsp.attributes
+                .push(attr("fee_distribution", fee_distribution));
+        }
         rsp.attributes.push(attr("from", self.from));
     }
 }
@@ -47,12 +94,20 @@ impl<'a> Event for RemoveSignerEvent<'a> {
 pub struct AddFoundryAssetEvent<'a> {
     pub from: &'a str,
     pub token: &'a str,
+    pub symbol: Option<&'a str>,
+    pub decimals: Option<u8>,
 }
 
 impl<'a> Event for AddFoundryAssetEvent<'a> {
     fn add_attributes(&self, rsp: &mut Response) {
         rsp.attributes.push(attr("action", "add_foundry_asset"));
         rsp.attributes.push(attr("token", self.token));
+        if let Some(symbol) = self.symbol {
+            rsp.attributes.push(attr("symbol", symbol));
+        }
+        if let Some(decimals) = self.decimals {
+            rsp.attributes.push(attr("decimals", decimals.to_string()));
+        }
         rsp.attributes.push(attr("from", self.from));
     }
 }
@@ -71,11 +126,132 @@ impl<'a> Event for RemoveFoundryAssetEvent<'a> {
     }
 }
 
+/// Tracks foundry assets delisted to withdraw-only
+pub struct DelistFoundryAssetEvent<'a> {
+    pub from: &'a str,
+    pub token: &'a str,
+}
+
+impl<'a> Event for DelistFoundryAssetEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "delist_foundry_asset"));
+        rsp.attributes.push(attr("token", self.token));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks foundry asset liquidity cap updates
+pub struct UpdateAssetLimitsEvent<'a> {
+    pub from: &'a str,
+    pub token: &'a str,
+    pub max_total_liquidity: Option<Uint128>,
+    pub max_per_provider: Option<Uint128>,
+}
+
+impl<'a> Event for UpdateAssetLimitsEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "update_asset_limits"));
+        rsp.attributes.push(attr("token", self.token));
+        if let Some(max_total_liquidity) = self.max_total_liquidity {
+            rsp.attributes
+                .push(attr("max_total_liquidity", max_total_liquidity));
+        }
+        if let Some(max_per_provider) = self.max_per_provider {
+            rsp.attributes
+                .push(attr("max_per_provider", max_per_provider));
+        }
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks liquidity provider allowlist additions
+pub struct AddLiquidityProviderEvent<'a> {
+    pub from: &'a str,
+    pub provider: &'a str,
+}
+
+impl<'a> Event for AddLiquidityProviderEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes
+            .push(attr("action", "add_liquidity_provider"));
+        rsp.attributes.push(attr("provider", self.provider));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks liquidity provider allowlist removals
+pub struct RemoveLiquidityProviderEvent<'a> {
+    pub from: &'a str,
+    pub provider: &'a str,
+}
+
+impl<'a> Event for RemoveLiquidityProviderEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes
+            .push(attr("action", "remove_liquidity_provider"));
+        rsp.attributes.push(attr("provider", self.provider));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks provider allowlist toggles, a missing token is the global toggle
+pub struct SetProviderAllowlistEvent<'a> {
+    pub from: &'a str,
+    pub token: Option<&'a str>,
+    pub enabled: bool,
+}
+
+impl<'a> Event for SetProviderAllowlistEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes
+            .push(attr("action", "set_provider_allowlist"));
+        if let Some(token) = self.token {
+            rsp.attributes.push(attr("token", token));
+        }
+        rsp.attributes
+            .push(attr("enabled", self.enabled.to_string()));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks operator approvals
+pub struct ApproveOperatorEvent<'a> {
+    pub owner: &'a str,
+    pub operator: &'a str,
+    pub expires: Expiration,
+}
+
+impl<'a> Event for ApproveOperatorEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "approve_operator"));
+        rsp.attributes.push(attr("owner", self.owner));
+        rsp.attributes.push(attr("operator", self.operator));
+        rsp.attributes
+            .push(attr("expires", self.expires.to_string()));
+    }
+}
+
+/// Tracks operator revocations
+pub struct RevokeOperatorEvent<'a> {
+    pub owner: &'a str,
+    pub operator: &'a str,
+}
+
+impl<'a> Event for RevokeOperatorEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "revoke_operator"));
+        rsp.attributes.push(attr("owner", self.owner));
+        rsp.attributes.push(attr("operator", self.operator));
+    }
+}
+
 /// Tracks liquidity additions
 pub struct AddLiquidityEvent<'a> {
     pub from: &'a str,
     pub token: &'a str,
     pub amount: Uint128,
+    pub shares: Uint128,
+    pub operator: Option<&'a str>,
 }
 
 impl<'a> Event for AddLiquidityEvent<'a> {
@@ -83,7 +259,11 @@ impl<'a> Event for AddLiquidityEvent<'a> {
         rsp.attributes.push(attr("action", "add_liquidity"));
         rsp.attributes.push(attr("token", self.token));
         rsp.attributes.push(attr("amount", self.amount));
+        rsp.attributes.push(attr("shares", self.shares));
         rsp.attributes.push(attr("from", self.from));
+        if let Some(operator) = self.operator {
+            rsp.attributes.push(attr("operator", operator));
+        }
     }
 }
 
@@ -92,6 +272,9 @@ pub struct RemoveLiquidityEvent<'a> {
     pub from: &'a str,
     pub token: &'a str,
     pub amount: Uint128,
+    pub shares: Uint128,
+    pub release_at: Option<Expiration>,
+    pub operator: Option<&'a str>,
 }
 
 impl<'a> Event for RemoveLiquidityEvent<'a> {
@@ -99,6 +282,99 @@ impl<'a> Event for RemoveLiquidityEvent<'a> {
         rsp.attributes.push(attr("action", "remove_liquidity"));
         rsp.attributes.push(attr("token", self.token));
         rsp.attributes.push(attr("amount", self.amount));
+        rsp.attributes.push(attr("shares", self.shares));
+        if let Some(release_at) = self.release_at {
+            rsp.attributes
+                .push(attr("release_at", release_at.to_string()));
+        }
+        rsp.attributes.push(attr("from", self.from));
+        if let Some(operator) = self.operator {
+            rsp.attributes.push(attr("operator", operator));
+        }
+    }
+}
+
+/// Tracks unbonding period updates
+pub struct SetUnbondingPeriodEvent<'a> {
+    pub from: &'a str,
+    pub token: &'a str,
+    pub unbonding_period: Option<Duration>,
+}
+
+impl<'a> Event for SetUnbondingPeriodEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "set_unbonding_period"));
+        rsp.attributes.push(attr("token", self.token));
+        if let Some(unbonding_period) = self.unbonding_period {
+            rsp.attributes
+                .push(attr("unbonding_period", unbonding_period.to_string()));
+        }
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks payouts of matured liquidity claims
+pub struct ClaimLiquidityEvent<'a> {
+    pub from: &'a str,
+    pub amount: &'a [Coin],
+}
+
+impl<'a> Event for ClaimLiquidityEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "claim_liquidity"));
+        let amount = self
+            .amount
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        rsp.attributes.push(attr("amount", amount));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks swap fee reward payouts
+pub struct ClaimRewardsEvent<'a> {
+    pub from: &'a str,
+    pub token: &'a str,
+    pub amount: Uint128,
+}
+
+impl<'a> Event for ClaimRewardsEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "claim_rewards"));
+        rsp.attributes.push(attr("token", self.token));
+        rsp.attributes.push(attr("amount", self.amount));
+        rsp.attributes.push(attr("from", self.from));
+    }
+}
+
+/// Tracks LP receipt token registrations
+pub struct RegisterLpTokenEvent<'a> {
+    pub token: &'a str,
+    pub lp_token: &'a str,
+}
+
+impl<'a> Event for RegisterLpTokenEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "register_lp_token"));
+        rsp.attributes.push(attr("token", self.token));
+        rsp.attributes.push(attr("lp_token", self.lp_token));
+    }
+}
+
+/// Tracks liquidity moved into LP receipt tokens
+pub struct WrapLiquidityEvent<'a> {
+    pub from: &'a str,
+    pub token: &'a str,
+    pub shares: Uint128,
+}
+
+impl<'a> Event for WrapLiquidityEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "wrap_liquidity"));
+        rsp.attributes.push(attr("token", self.token));
+        rsp.attributes.push(attr("shares", self.shares));
         rsp.attributes.push(attr("from", self.from));
     }
 }
@@ -108,6 +384,7 @@ pub struct BridgeSwapEvent<'a> {
     pub from: &'a str,
     pub token: &'a str,
     pub amount: Uint128,
+    pub fee: Uint128,
     pub target_chain_id: &'a str,
     pub target_token: &'a str,
     pub target_address: &'a str,
@@ -119,6 +396,7 @@ impl<'a> Event for BridgeSwapEvent<'a> {
         rsp.attributes.push(attr("from", self.from));
         rsp.attributes.push(attr("token", self.token));
         rsp.attributes.push(attr("amount", self.amount));
+        rsp.attributes.push(attr("fee", self.fee));
         rsp.attributes
             .push(attr("target_chain_id", self.target_chain_id));
         rsp.attributes.push(attr("target_token", self.target_token));
@@ -127,6 +405,21 @@ impl<'a> Event for BridgeSwapEvent<'a> {
     }
 }
 
+/// Tracks withdraw calls that failed and were paid with a plain transfer
+pub struct WithdrawCallFallbackEvent<'a> {
+    pub payee: &'a str,
+    pub error: &'a str,
+}
+
+impl<'a> Event for WithdrawCallFallbackEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes
+            .push(attr("action", "withdraw_call_fallback"));
+        rsp.attributes.push(attr("payee", self.payee));
+        rsp.attributes.push(attr("error", self.error));
+    }
+}
+
 /// Tracks withdraw signed events
 pub struct BridgeWithdrawSignedEvent<'a> {
     pub from: &'a str,
@@ -134,7 +427,8 @@ pub struct BridgeWithdrawSignedEvent<'a> {
     pub token: &'a str,
     pub amount: Uint128,
     pub salt: &'a str,
-    pub signature: &'a str,
+    pub signatures: &'a [String],
+    pub signers: &'a [String],
 }
 
 impl<'a> Event for BridgeWithdrawSignedEvent<'a> {
@@ -145,6 +439,8 @@ impl<'a> Event for BridgeWithdrawSignedEvent<'a> {
         rsp.attributes.push(attr("token", self.token));
         rsp.attributes.push(attr("amount", self.amount));
         rsp.attributes.push(attr("salt", self.salt));
-        rsp.attributes.push(attr("signature", self.signature));
+        rsp.attributes
+            .push(attr("signatures", self.signatures.join(",")));
+        rsp.attributes.push(attr("signers", self.signers.join(",")));
     }
 }
@@ -1,13 +1,28 @@
 pub use crate::event::{
-    AddFoundryAssetEvent, AddLiquidityEvent, AddSignerEvent, BridgeSwapEvent,
-    BridgeWithdrawSignedEvent, RemoveFoundryAssetEvent, RemoveLiquidityEvent, RemoveSignerEvent,
-    TransferOwnershipEvent,
+    AddFoundryAssetEvent, AddLiquidityEvent, AddLiquidityProviderEvent, AddSignerEvent,
+    ApproveOperatorEvent, BridgeSwapEvent, BridgeWithdrawSignedEvent, ClaimLiquidityEvent,
+    ClaimRewardsEvent, DelistFoundryAssetEvent, RegisterLpTokenEvent, RemoveFoundryAssetEvent,
+    RemoveLiquidityEvent, RemoveLiquidityProviderEvent, RemoveSignerEvent, RevokeOperatorEvent,
+    SetProviderAllowlistEvent, SetSwapFeeEvent, SetThresholdEvent, SetUnbondingPeriodEvent,
+    TransferOwnershipEvent, UpdateAssetLimitsEvent, WithdrawCallFallbackEvent, WrapLiquidityEvent,
 };
 pub use crate::helpers::MultiswapContract;
-pub use crate::msg::{MigrateMsg, MultiswapExecuteMsg};
-pub use crate::query::{Liquidity, MultiswapQueryMsg};
+pub use crate::msg::{
+    Cw20HookMsg, MigrateMsg, MultiswapExecuteMsg, WithdrawSignMessage, WithdrawSignature,
+    WithdrawSignedItem,
+};
+pub use crate::query::{
+    Approval, AssetInfo, AssetStatus, Claim, Config, FeeDistribution, FoundryAsset, KeyType,
+    Liquidity, MultiswapQueryMsg, PoolInfo, PoolTotal, SignMode, SignatureVerification, Signer,
+    SignerInfo, SignerStatus, VerifyWithdrawResponse,
+};
+pub use crate::signing::{
+    eip712_domain_separator, eip712_withdraw_hash, eip712_withdraw_struct_hash, eth_message_hash,
+    keccak256, EIP712_DOMAIN_TYPE, WITHDRAW_TYPE,
+};
 
 mod event;
 mod helpers;
 mod msg;
 mod query;
+mod signing;
@@ -2,6 +2,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Binary, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::{Duration, Expiration};
+
+use crate::query::{AssetInfo, FeeDistribution, KeyType, SignMode};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -11,33 +15,126 @@ pub enum MultiswapExecuteMsg {
     },
     AddSigner {
         signer: String,
+        key_type: Option<KeyType>,
+        /// Block height from which the signer is accepted, immediately if omitted
+        active_from: Option<u64>,
     },
     RemoveSigner {
         signer: String,
+        /// Last block height at which the signer is accepted, removed immediately if omitted
+        valid_until: Option<u64>,
+    },
+    SetThreshold {
+        threshold: u32,
+    },
+    SetSwapFee {
+        swap_fee_bps: u16,
+        /// Keeps the current distribution if not set
+        #[serde(default)]
+        fee_distribution: Option<FeeDistribution>,
     },
     AddFoundryAsset {
         token: String,
+        /// Native denom `token` if not set, cw20 assets are registered under their contract address
+        #[serde(default)]
+        asset_info: Option<AssetInfo>,
+        /// Read from the token contract for cw20 assets if not set
+        #[serde(default)]
+        symbol: Option<String>,
+        /// Read from the token contract for cw20 assets if not set
+        #[serde(default)]
+        decimals: Option<u8>,
     },
+    /// Delists an active foundry asset to withdraw-only,
+    /// removes a withdraw-only asset once all liquidity is withdrawn
     RemoveFoundryAsset {
         token: String,
     },
-    AddLiquidity {
+    /// Sets the liquidity caps of a foundry asset, unset caps are removed
+    UpdateAssetLimits {
         token: String,
+        max_total_liquidity: Option<Uint128>,
+        max_per_provider: Option<Uint128>,
+    },
+    AddLiquidityProvider {
+        provider: String,
+    },
+    RemoveLiquidityProvider {
+        provider: String,
+    },
+    /// Toggles the provider allowlist of a foundry asset, or of all assets if no token is set
+    SetProviderAllowlist {
+        token: Option<String>,
+        enabled: bool,
+    },
+    /// Adds liquidity for `owner` if set, the sender must be an approved operator of the owner
+    AddLiquidity {
+        /// Bank denom deposited as attached funds, cw20 assets are added through `Receive`
+        asset_info: AssetInfo,
         amount: Uint128,
+        #[serde(default)]
+        owner: Option<String>,
     },
+    /// Removes liquidity of `owner` if set, the sender must be an approved operator of the owner
     RemoveLiquidity {
         token: String,
-        amount: Uint128,
+        shares: Uint128,
+        #[serde(default)]
+        owner: Option<String>,
+    },
+    /// Allows an operator to add and remove liquidity on behalf of the sender
+    ApproveOperator {
+        operator: String,
+        /// Never expires if not set
+        #[serde(default)]
+        expires: Option<Expiration>,
     },
+    RevokeOperator {
+        operator: String,
+    },
+    /// Sets the time liquidity removals of a foundry asset are locked before they can be
+    /// claimed, removals are paid out immediately without an unbonding period
+    SetUnbondingPeriod {
+        token: String,
+        unbonding_period: Option<Duration>,
+    },
+    /// Pays out all matured liquidity claims of the sender
+    ClaimLiquidity {},
+    /// Pays out the swap fee rewards of the sender
+    ClaimRewards {
+        token: String,
+    },
+    /// Instantiates the LP receipt token of a foundry asset added before LP tokens were enabled
+    CreateLpToken {
+        token: String,
+    },
+    /// Moves shares held in the pool into transferable LP receipt tokens
+    WrapLiquidity {
+        token: String,
+        shares: Uint128,
+    },
+    /// Handles LP receipt tokens sent to the pool, see `Cw20HookMsg`
+    Receive(Cw20ReceiveMsg),
     WithdrawSigned {
         payee: String,
         salt: String,
-        token: String,
+        asset_info: AssetInfo,
         amount: Uint128,
-        signature: String,
+        signatures: Vec<WithdrawSignature>,
+        #[serde(default)]
+        expires_at: Expiration,
+        /// Executes the payee contract with the withdrawn funds attached
+        msg: Option<Binary>,
+    },
+    WithdrawSignedBatch {
+        items: Vec<WithdrawSignedItem>,
+        /// Skips items that were already processed instead of failing the whole batch
+        #[serde(default)]
+        skip_processed: bool,
     },
     Swap {
-        token: String,
+        /// Bank denom deposited as attached funds, cw20 assets are swapped through `Receive`
+        asset_info: AssetInfo,
         amount: Uint128,
         target_chain_id: String,
         target_token: String,
@@ -45,5 +142,68 @@ pub enum MultiswapExecuteMsg {
     },
 }
 
+/// Signed withdrawal, as executed by `WithdrawSigned`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawSignedItem {
+    pub payee: String,
+    /// Signed as its token, the denom or cw20 contract address
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+    pub salt: String,
+    pub signatures: Vec<WithdrawSignature>,
+    #[serde(default)]
+    pub expires_at: Expiration,
+    pub msg: Option<Binary>,
+}
+
+/// Signature of a withdraw message hash.
+/// `signer` can be omitted for Ethereum signers as it is recovered from the signature,
+/// other key types must provide the registered public key.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawSignature {
+    pub signer: Option<String>,
+    pub signature: String,
+}
+
+/// Message signed by the bridge signers to authorize a withdrawal
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawSignMessage {
+    pub chain_id: String,
+    pub payee: String,
+    pub token: String,
+    pub amount: Uint128,
+    pub salt: String,
+    pub expires_at: Expiration,
+    /// Omitted when there is no callback, so plain withdrawals sign the same message as before
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg: Option<Binary>,
+}
+
+/// Messages attached to LP receipt tokens and cw20 foundry assets sent to the pool
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Burns the received LP tokens and pays out the underlying liquidity to the sender
+    RemoveLiquidity {},
+    /// Adds the received cw20 foundry asset as liquidity of `owner`, or of the sender if not set
+    AddLiquidity {
+        #[serde(default)]
+        owner: Option<String>,
+    },
+    /// Bridges the received cw20 foundry asset to the target chain
+    Swap {
+        target_chain_id: String,
+        target_token: String,
+        target_address: String,
+    },
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Code id of the cw20 contract used for LP receipt tokens
+    #[serde(default)]
+    pub lp_token_code_id: Option<u64>,
+    /// Hashing scheme used for withdraw signatures, unchanged when omitted
+    #[serde(default)]
+    pub sign_mode: Option<SignMode>,
+}
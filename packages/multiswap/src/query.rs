@@ -1,22 +1,355 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Api, StdResult, Uint128};
+use cosmwasm_std::{Api, Binary, StdResult, Uint128};
+use cw_utils::Expiration;
+
+use crate::msg::WithdrawSignature;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum MultiswapQueryMsg {
-    Liquidity { owner: String, token: String },
+    Liquidity {
+        owner: String,
+        token: String,
+    },
     AllLiquidity {},
     Owner {},
-    Signers {},
-    FoundryAssets {},
+    /// Lists the signers with their rotation status, retired signers included
+    Signers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the foundry assets with their metadata
+    FoundryAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the metadata of a foundry asset
+    FoundryAsset {
+        token: String,
+    },
+    /// Returns the pool totals of a token
+    Pool {
+        token: String,
+    },
+    /// Returns the liquidity of an owner at the start of a block height. Heights before the
+    /// liquidity history was recorded return the oldest known position. Tokens with an LP
+    /// receipt token are rejected, receipt balances are kept by the cw20 contract without history.
+    LiquidityAtHeight {
+        owner: String,
+        token: String,
+        height: u64,
+    },
+    /// Returns the pool share and liquidity totals at the start of a block height
+    PoolTotalAtHeight {
+        token: String,
+        height: u64,
+    },
+    /// Lists the pool totals of all tokens
+    Pools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the allowlisted liquidity providers
+    LiquidityProviders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the rewards an owner can claim for a token
+    PendingRewards {
+        owner: String,
+        token: String,
+    },
+    /// Returns the unbonding period of a foundry asset, if any
+    UnbondingPeriod {
+        token: String,
+    },
+    /// Returns the pending liquidity claims of an owner
+    Claims {
+        owner: String,
+    },
+    /// Lists the unexpired operators approved by an owner
+    Operators {
+        owner: String,
+    },
+    /// Returns the LP receipt token address of a foundry asset, if any
+    LpToken {
+        token: String,
+    },
+    Config {},
+    UsedMessage {
+        hash: String,
+    },
+    IsWithdrawProcessed {
+        payee: String,
+        asset_info: AssetInfo,
+        amount: Uint128,
+        salt: String,
+        #[serde(default)]
+        expires_at: Expiration,
+        msg: Option<Binary>,
+    },
+    /// Runs the `WithdrawSigned` checks without executing it
+    VerifyWithdraw {
+        payee: String,
+        asset_info: AssetInfo,
+        amount: Uint128,
+        salt: String,
+        signatures: Vec<WithdrawSignature>,
+        #[serde(default)]
+        expires_at: Expiration,
+        msg: Option<Binary>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VerifyWithdrawResponse {
+    /// Hex encoded hash of the signed withdraw message
+    pub message_hash: String,
+    pub signatures: Vec<SignatureVerification>,
+    pub used: bool,
+    pub sufficient_liquidity: bool,
+    /// Error `WithdrawSigned` would fail with, if any
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SignatureVerification {
+    /// Provided or recovered signer
+    pub signer: Option<String>,
+    pub registered: bool,
+    /// Error the signature is rejected with, if any
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Config {
+    /// Minimum number of distinct registered signers required for a withdrawal
+    pub threshold: u32,
+    /// Hashing scheme used for withdraw signatures
+    #[serde(default)]
+    pub sign_mode: SignMode,
+    /// Share of every swap retained by the pool for liquidity providers, in basis points
+    #[serde(default)]
+    pub swap_fee_bps: u16,
+    /// How retained swap fees are distributed to liquidity providers
+    #[serde(default)]
+    pub fee_distribution: FeeDistribution,
+    /// Code id of the cw20 contract instantiated as LP receipt token for new foundry assets
+    #[serde(default)]
+    pub lp_token_code_id: Option<u64>,
+    /// Restricts liquidity deposits of all foundry assets to allowlisted providers
+    #[serde(default)]
+    pub provider_allowlist: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignMode {
+    /// `personal_sign` over the JSON encoded `WithdrawSignMessage`
+    #[default]
+    Eip191,
+    /// EIP-712 typed data bound to the contract address and chain id
+    Eip712,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeDistribution {
+    /// Swap fees are added to the pool liquidity and raise the value of every share
+    #[default]
+    Compound,
+    /// Swap fees bump a per-token reward index and are paid out with `ClaimRewards`.
+    /// Shares wrapped into LP receipt tokens do not earn rewards.
+    RewardIndex,
+}
+
+/// Foundry asset accepted by the bridge
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FoundryAsset {
+    /// Bank denom or cw20 contract address
+    pub token: String,
+    /// Ticker of the token, read from the token contract for cw20 assets if not set
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Decimal places of token amounts, read from the token contract for cw20 assets if not set
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Block height the asset was listed at, not set for assets listed before metadata
+    #[serde(default)]
+    pub added_at: Option<u64>,
+    /// Maximum liquidity the pool accepts, unlimited if not set
+    #[serde(default)]
+    pub max_total_liquidity: Option<Uint128>,
+    /// Maximum liquidity a single provider may hold, unlimited if not set
+    #[serde(default)]
+    pub max_per_provider: Option<Uint128>,
+    /// Only allowlisted providers may add liquidity
+    #[serde(default)]
+    pub provider_allowlist: bool,
+    /// Whether the asset is enabled, delisted assets only allow liquidity removal
+    #[serde(default)]
+    pub status: AssetStatus,
+    /// `token` is the address of a cw20 contract instead of a bank denom
+    #[serde(default)]
+    pub cw20: bool,
+}
+
+impl FoundryAsset {
+    pub fn asset_info(&self) -> AssetInfo {
+        if self.cw20 {
+            return AssetInfo::Cw20 {
+                contract_addr: self.token.to_string(),
+            };
+        }
+        AssetInfo::Native {
+            denom: self.token.to_string(),
+        }
+    }
+}
+
+/// Token backing a foundry asset
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    /// Bank denom, deposited as attached funds
+    Native { denom: String },
+    /// cw20 contract, deposited through its `send` hook
+    Cw20 { contract_addr: String },
+}
+
+impl AssetInfo {
+    /// Returns the token the asset is registered under, its denom or contract address
+    pub fn token(&self) -> &str {
+        match self {
+            AssetInfo::Native { denom } => denom,
+            AssetInfo::Cw20 { contract_addr } => contract_addr,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetStatus {
+    /// Accepts liquidity, swaps and signed withdrawals
+    #[default]
+    Active,
+    /// Delisted, providers can only remove their liquidity
+    WithdrawOnly,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Signer {
+    /// Ethereum address for `eth_secp256k1`, hex encoded public key otherwise
+    pub signer: String,
+    pub key_type: KeyType,
+    #[serde(default)]
+    pub active_from: Option<u64>,
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+}
+
+impl Signer {
+    pub fn status(&self, height: u64) -> SignerStatus {
+        if let Some(active_from) = self.active_from {
+            if height < active_from {
+                return SignerStatus::Pending;
+            }
+        }
+        match self.valid_until {
+            Some(valid_until) if height > valid_until => SignerStatus::Retired,
+            Some(_) => SignerStatus::Retiring,
+            None => SignerStatus::Active,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerStatus {
+    /// Waiting for `active_from`
+    Pending,
+    Active,
+    /// Scheduled for removal, still accepted until `valid_until`
+    Retiring,
+    /// Past `valid_until`, no longer accepted
+    Retired,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SignerInfo {
+    pub signer: String,
+    pub key_type: KeyType,
+    pub active_from: Option<u64>,
+    pub valid_until: Option<u64>,
+    pub status: SignerStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    /// Ethereum address, signer is recovered from the signature
+    #[default]
+    EthSecp256k1,
+    /// Compressed secp256k1 public key, as used by Cosmos accounts
+    CosmosSecp256k1,
+    /// Ed25519 public key, as used by Tendermint validators
+    Ed25519,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PoolInfo {
+    pub token: String,
+    /// Shares issued to providers, including LP receipt tokens
+    pub total_shares: Uint128,
+    /// Liquidity provided plus retained swap fees, redeemable by the shares
+    pub total_liquidity: Uint128,
+    /// Pool balance available for bridge withdrawals
+    pub available_liquidity: Uint128,
+    /// Sum of all swap deposits
+    pub total_bridged_in: Uint128,
+    /// Sum of all signed withdrawals
+    pub total_bridged_out: Uint128,
+    /// Number of providers holding shares in the pool, LP receipt token holders are not counted
+    pub provider_count: u64,
+}
+
+/// Pool totals at a past block height
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PoolTotal {
+    pub token: String,
+    pub height: u64,
+    pub total_shares: Uint128,
+    pub total_liquidity: Uint128,
+}
+
+/// Removed liquidity that can be claimed once `release_at` has expired
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Claim {
+    pub token: String,
+    pub amount: Uint128,
+    pub release_at: Expiration,
+    /// How the token is paid out, kept so claims outlive the asset listing.
+    /// Claims created before cw20 assets are bank denoms.
+    #[serde(default)]
+    pub asset_info: Option<AssetInfo>,
+}
+
+/// Operator allowed to manage the liquidity of an owner until `expires`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Approval {
+    pub operator: String,
+    pub expires: Expiration,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Liquidity {
     pub user: String,
     pub token: String,
+    /// Pool shares held by the user
+    pub shares: Uint128,
+    /// Amount of `token` the shares can currently be redeemed for
     pub amount: Uint128,
 }
 
@@ -25,6 +358,7 @@ impl Liquidity {
         Ok(Liquidity {
             user: self.user.to_string(),
             token: self.token.to_string(),
+            shares: self.shares,
             amount: self.amount,
         })
     }
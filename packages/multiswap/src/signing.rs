@@ -0,0 +1,203 @@
+use sha3::{Digest, Keccak256};
+
+use cw_utils::Expiration;
+
+use crate::msg::WithdrawSignMessage;
+
+/// EIP-712 domain type. Cosmos chain ids and contract addresses are not numeric or
+/// 20-byte values, so both are encoded as strings.
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,string chainId,string verifyingContract)";
+/// EIP-712 type of the withdraw message signed by the bridge signers.
+/// `expiresAtHeight` and `expiresAtTime` (in nanoseconds) are zero unless the message
+/// expires at a block height or time respectively, `msg` is empty without a payee callback.
+pub const WITHDRAW_TYPE: &str = "Withdraw(string payee,string token,uint256 amount,string salt,uint64 expiresAtHeight,uint64 expiresAtTime,bytes msg)";
+
+pub fn keccak256(data: &[u8]) -> Vec<u8> {
+    Keccak256::digest(data).to_vec()
+}
+
+/// Hashes a message the same way as `personal_sign` (EIP-191)
+pub fn eth_message_hash(message: &[u8]) -> Vec<u8> {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(&prefixed)
+}
+
+/// Calculates the EIP-712 domain separator of a multiswap deployment
+pub fn eip712_domain_separator(
+    name: &str,
+    version: &str,
+    chain_id: &str,
+    verifying_contract: &str,
+) -> Vec<u8> {
+    let mut encoded = keccak256(EIP712_DOMAIN_TYPE.as_bytes());
+    encoded.extend(keccak256(name.as_bytes()));
+    encoded.extend(keccak256(version.as_bytes()));
+    encoded.extend(keccak256(chain_id.as_bytes()));
+    encoded.extend(keccak256(verifying_contract.as_bytes()));
+    keccak256(&encoded)
+}
+
+/// Calculates the EIP-712 struct hash of a withdraw message.
+/// The chain id is bound through the domain separator.
+pub fn eip712_withdraw_struct_hash(message: &WithdrawSignMessage) -> Vec<u8> {
+    let (expires_at_height, expires_at_time) = match message.expires_at {
+        Expiration::AtHeight(height) => (height, 0),
+        Expiration::AtTime(time) => (0, time.nanos()),
+        Expiration::Never {} => (0, 0),
+    };
+    let mut encoded = keccak256(WITHDRAW_TYPE.as_bytes());
+    encoded.extend(keccak256(message.payee.as_bytes()));
+    encoded.extend(keccak256(message.token.as_bytes()));
+    encoded.extend(encode_uint256(message.amount.u128()));
+    encoded.extend(keccak256(message.salt.as_bytes()));
+    encoded.extend(encode_uint256(expires_at_height as u128));
+    encoded.extend(encode_uint256(expires_at_time as u128));
+    let msg = message
+        .msg
+        .as_ref()
+        .map(|msg| msg.as_slice())
+        .unwrap_or_default();
+    encoded.extend(keccak256(msg));
+    keccak256(&encoded)
+}
+
+/// Calculates the EIP-712 typed data hash that is signed for a withdrawal
+pub fn eip712_withdraw_hash(domain_separator: &[u8], message: &WithdrawSignMessage) -> Vec<u8> {
+    eip712_hash(domain_separator, &eip712_withdraw_struct_hash(message))
+}
+
+/// Calculates the `eth_signTypedData` hash of a struct hash in a domain
+fn eip712_hash(domain_separator: &[u8], struct_hash: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![0x19, 0x01];
+    encoded.extend_from_slice(domain_separator);
+    encoded.extend_from_slice(struct_hash);
+    keccak256(&encoded)
+}
+
+/// Left pads an unsigned integer to a 32 bytes word
+fn encode_uint256(value: u128) -> Vec<u8> {
+    let mut encoded = vec![0u8; 16];
+    encoded.extend_from_slice(&value.to_be_bytes());
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+    use cosmwasm_std::{Api, Uint128};
+
+    /// Left pads a 20 bytes address to a 32 bytes word
+    fn encode_address(address: &str) -> Vec<u8> {
+        let mut encoded = vec![0u8; 12];
+        encoded.extend(hex::decode(&address[2..]).unwrap());
+        encoded
+    }
+
+    /// Recovers the Ethereum address of a `r || s || v` signature
+    fn recover_address(hash: &[u8], signature: &str) -> String {
+        let signature = hex::decode(&signature[2..]).unwrap();
+        let pubkey = MockApi::default()
+            .secp256k1_recover_pubkey(hash, &signature[..64], signature[64] - 27)
+            .unwrap();
+        format!("0x{}", hex::encode(&keccak256(&pubkey[1..])[12..]))
+    }
+
+    /// Example of the EIP-712 specification, signed by `keccak256("cow")`
+    #[test]
+    fn eip712_specification_vector() {
+        let mut domain = keccak256(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        );
+        domain.extend(keccak256(b"Ether Mail"));
+        domain.extend(keccak256(b"1"));
+        domain.extend(encode_uint256(1));
+        domain.extend(encode_address("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"));
+        let domain_separator = keccak256(&domain);
+        assert_eq!(
+            hex::encode(&domain_separator),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+
+        let person = |name: &str, wallet: &str| {
+            let mut encoded = keccak256(b"Person(string name,address wallet)");
+            encoded.extend(keccak256(name.as_bytes()));
+            encoded.extend(encode_address(wallet));
+            keccak256(&encoded)
+        };
+        let mut mail = keccak256(
+            b"Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        );
+        mail.extend(person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"));
+        mail.extend(person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"));
+        mail.extend(keccak256(b"Hello, Bob!"));
+        let struct_hash = keccak256(&mail);
+        assert_eq!(
+            hex::encode(&struct_hash),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+
+        let hash = eip712_hash(&domain_separator, &struct_hash);
+        assert_eq!(
+            hex::encode(&hash),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        let signature = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c";
+        assert_eq!(
+            recover_address(&hash, signature),
+            "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+    }
+
+    /// Withdrawal signed by `keccak256("cow")` with `eth_signTypedData_v4`, the typed data is
+    /// `{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},
+    /// {"name":"chainId","type":"string"},{"name":"verifyingContract","type":"string"}],
+    /// "Withdraw":[{"name":"payee","type":"string"},{"name":"token","type":"string"},
+    /// {"name":"amount","type":"uint256"},{"name":"salt","type":"string"},
+    /// {"name":"expiresAtHeight","type":"uint64"},{"name":"expiresAtTime","type":"uint64"},
+    /// {"name":"msg","type":"bytes"}]},"primaryType":"Withdraw",
+    /// "domain":{"name":"crates.io:multiswap-base","version":"0.0.1","chainId":"cudos-1",
+    /// "verifyingContract":"cudos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9strccpl"},
+    /// "message":{"payee":"cudos1nysrj2xxpm77xpkvglne0zcvnxuq0laacc7nrv","token":"acudos",
+    /// "amount":"1000000","salt":"0x1","expiresAtHeight":12345,"expiresAtTime":0,"msg":"0x"}}`
+    #[test]
+    fn eip712_withdraw_vector() {
+        let domain_separator = eip712_domain_separator(
+            "crates.io:multiswap-base",
+            "0.0.1",
+            "cudos-1",
+            "cudos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9strccpl",
+        );
+        assert_eq!(
+            hex::encode(&domain_separator),
+            "b8d11b7f5fae36b9d9aa48c5d95ad9704e956f934bc929c270b541e5cf5c5c12"
+        );
+
+        let message = WithdrawSignMessage {
+            chain_id: "cudos-1".to_string(),
+            payee: "cudos1nysrj2xxpm77xpkvglne0zcvnxuq0laacc7nrv".to_string(),
+            token: "acudos".to_string(),
+            amount: Uint128::new(1000000),
+            salt: "0x1".to_string(),
+            expires_at: Expiration::AtHeight(12345),
+            msg: None,
+        };
+        assert_eq!(
+            hex::encode(eip712_withdraw_struct_hash(&message)),
+            "72834e1dab97fb0e03c618df2d28cd6d90c2c4b3726bb671f7ace48b9dc53156"
+        );
+
+        let hash = eip712_withdraw_hash(&domain_separator, &message);
+        assert_eq!(
+            hex::encode(&hash),
+            "b5b0c1ff6ddbd809c74be9502224112b89de841c319869aa83e8f307120bea82"
+        );
+        let signature = "0xf9df09cca6a4bc72989229d5175a017a95fe92cef21399f5ceab54994e6c0f206a563a2de8c4465c3d9430bfc016d198b6a225f82eaa68180d1aaccec882c24e1c";
+        assert_eq!(
+            recover_address(&hash, signature),
+            "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+    }
+}
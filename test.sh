@@ -7,23 +7,61 @@
 # deploy without admin
 # cudos-noded tx wasm instantiate 1 '{"owner":"'$VALIDATOR'"}' --from=validator --label "FerrumMultiswap" --chain-id=test --gas=auto --gas-adjustment=1.3 -b=block --keyring-backend=test --no-admin -y
 # CONTRACT=cudos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9strccpl
-# cudos-noded tx wasm execute $CONTRACT '{"add_foundry_asset":{"token":"stake"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_foundry_asset":{"token":"stake","symbol":"STAKE","decimals":6}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"create_lp_token":{"token":"stake"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"set_unbonding_period":{"token":"stake","unbonding_period":{"time":604800}}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"update_asset_limits":{"token":"stake","max_total_liquidity":"100000000","max_per_provider":"10000000"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"set_provider_allowlist":{"token":"stake","enabled":true}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_liquidity_provider":{"provider":"'$VALIDATOR'"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# the first removal delists the asset to withdraw-only, the second removes it once all liquidity is withdrawn
 # cudos-noded tx wasm execute $CONTRACT '{"remove_foundry_asset":{"token":"stake"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"add_liquidity":{"token":"stake","amount": "1000000"}}' --amount=1000000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"remove_liquidity":{"token":"stake","amount": "100000"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"withdraw_signed":{"payee":"'$VALIDATOR'","token":"stake","amount":"1000","salt":"0x00","signature":"0x00"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"swap":{"token":"stake","amount":"1000","target_chain_id":"0x00","target_token":"0x00","target_address":"0x00"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"add_signer":{"signer":"cudos1nysrj2xxpm77xpkvglne0zcvnxuq0laacc7nrv"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $CONTRACT '{"remove_signer":{"signer":"cudos1nysrj2xxpm77xpkvglne0zcvnxuq0laacc7nrv"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_liquidity":{"asset_info":{"native":{"denom":"stake"}},"amount": "1000000"}}' --amount=1000000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"remove_liquidity":{"token":"stake","shares": "100000"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# OPERATOR=$(cudos-noded keys show -a operator --keyring-backend=test)
+# cudos-noded tx wasm execute $CONTRACT '{"approve_operator":{"operator":"'$OPERATOR'","expires":{"at_height":100000}}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_liquidity":{"asset_info":{"native":{"denom":"stake"}},"amount":"1000000","owner":"'$VALIDATOR'"}}' --amount=1000000stake --from=operator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"revoke_operator":{"operator":"'$OPERATOR'"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"claim_liquidity":{}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"claim_rewards":{"token":"stake"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"wrap_liquidity":{"token":"stake","shares": "100000"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $LP_TOKEN '{"send":{"contract":"'$CONTRACT'","amount":"100000","msg":"eyJyZW1vdmVfbGlxdWlkaXR5Ijp7fX0="}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"withdraw_signed":{"payee":"'$VALIDATOR'","asset_info":{"native":{"denom":"stake"}},"amount":"1000","salt":"0x00","signatures":[{"signature":"0x00"}]}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"swap":{"asset_info":{"native":{"denom":"stake"}},"amount":"1000","target_chain_id":"0x00","target_token":"0x00","target_address":"0x00"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# CW20_TOKEN=cudos1...
+# cudos-noded tx wasm execute $CONTRACT '{"add_foundry_asset":{"token":"'$CW20_TOKEN'","asset_info":{"cw20":{"contract_addr":"'$CW20_TOKEN'"}}}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CW20_TOKEN '{"send":{"contract":"'$CONTRACT'","amount":"1000000","msg":"eyJhZGRfbGlxdWlkaXR5Ijp7fX0="}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CW20_TOKEN '{"send":{"contract":"'$CONTRACT'","amount":"1000","msg":"eyJzd2FwIjp7InRhcmdldF9jaGFpbl9pZCI6IjB4MDAiLCJ0YXJnZXRfdG9rZW4iOiIweDAwIiwidGFyZ2V0X2FkZHJlc3MiOiIweDAwIn19"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_signer":{"signer":"0x4a62316623ad457f02cdc5d997ded67a383ec569"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"add_signer":{"signer":"ed4928c628d1c2c6eae90338905995612959273a5c63f93636c14614ac8737d1","key_type":"ed25519"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"remove_signer":{"signer":"0x4a62316623ad457f02cdc5d997ded67a383ec569"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# rotate signers: the new signer is accepted from height 1000, the old one until height 1100
+# cudos-noded tx wasm execute $CONTRACT '{"add_signer":{"signer":"0x99c851eaa3c3976914d63b822c67e201ec0bfbb8","active_from":1000}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"remove_signer":{"signer":"0x4a62316623ad457f02cdc5d997ded67a383ec569","valid_until":1100}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"set_threshold":{"threshold":2}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $CONTRACT '{"set_swap_fee":{"swap_fee_bps":30,"fee_distribution":"reward_index"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
 # cudos-noded tx wasm execute $CONTRACT '{"transfer_ownership":{"new_owner":"cudos1nysrj2xxpm77xpkvglne0zcvnxuq0laacc7nrv"}}' --amount=1000stake --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
 
 # cudos-noded query wasm contract-state smart $CONTRACT '{"liquidity":{"owner":"'$VALIDATOR'","token":"stake"}}'
 # cudos-noded query wasm contract-state smart $CONTRACT '{"all_liquidity":{}}'
-# cudos-noded query wasm contract-state smart $CONTRACT '{"signers":{}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"pool":{"token":"stake"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"pools":{"limit":10}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"foundry_assets":{"limit":10}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"foundry_asset":{"token":"stake"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"liquidity_at_height":{"owner":"'$VALIDATOR'","token":"stake","height":1000}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"pool_total_at_height":{"token":"stake","height":1000}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"liquidity_providers":{"limit":10}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"lp_token":{"token":"stake"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"claims":{"owner":"'$VALIDATOR'"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"operators":{"owner":"'$VALIDATOR'"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"pending_rewards":{"owner":"'$VALIDATOR'","token":"stake"}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"signers":{"limit":30}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"config":{}}'
+# cudos-noded query wasm contract-state smart $CONTRACT '{"is_withdraw_processed":{"payee":"'$VALIDATOR'","asset_info":{"native":{"denom":"stake"}},"amount":"1000","salt":"0x00"}}'
 
 # cudos-noded tx wasm store cw-plus/multiswap_base.wasm --from=validator --keyring-backend=test --chain-id=test --node http://localhost:26657 --gas=auto --gas-adjustment=1.3 -y
 # NEW_CODEID=2
 # cudos-noded tx wasm migrate $CONTRACT $NEW_CODEID '{}' --from=validator --keyring-backend=test --chain-id=test --node http://localhost:26657 --gas=auto --gas-adjustment=1.3 -y
+# cudos-noded tx wasm migrate $CONTRACT $NEW_CODEID '{"sign_mode":"eip712"}' --from=validator --keyring-backend=test --chain-id=test --node http://localhost:26657 --gas=auto --gas-adjustment=1.3 -y
 
 # upgrade ownership transfer
 # cudos-noded tx wasm set-contract-admin $CONTRACT $VALIDATOR --from=validator --keyring-backend=test --chain-id=test --node http://localhost:26657 --gas=auto --gas-adjustment=1.3 -y
@@ -34,7 +72,7 @@
 # cudos-noded tx wasm instantiate 2 '{"owner":"'$VALIDATOR'","pool":"'$CONTRACT'"}' --from=validator --label "FerrumFiberRouter" --chain-id=test --gas=auto --gas-adjustment=1.3 -b=block --keyring-backend=test --admin=$VALIDATOR -y
 # FIBER_ROUTER=cudos1nc5tatafv6eyq7llkr2gv50ff9e22mnf70qgjlv737ktmt4eswrq8ka6re
 # cudos-noded tx wasm execute $FIBER_ROUTER '{"set_pool":{"pool":"'$CONTRACT'"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $FIBER_ROUTER '{"withdraw_signed":{"payee":"'$VALIDATOR'","token":"stake","amount":"1000","salt":"0x00","signature":"0x00"}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
-# cudos-noded tx wasm execute $FIBER_ROUTER '{"swap":{"token":"stake","amount":"1000","target_chain_id":"0x00","target_token":"0x00","target_address":"0x00"}}' --amount=1000stake  --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $FIBER_ROUTER '{"withdraw_signed":{"payee":"'$VALIDATOR'","asset_info":{"native":{"denom":"stake"}},"amount":"1000","salt":"0x00","signatures":[{"signature":"0x00"}]}}' --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
+# cudos-noded tx wasm execute $FIBER_ROUTER '{"swap":{"asset_info":{"native":{"denom":"stake"}},"amount":"1000","target_chain_id":"0x00","target_token":"0x00","target_address":"0x00"}}' --amount=1000stake  --from=validator --gas=auto --gas-adjustment=1.3 --chain-id=test -y --keyring-backend=test
 # cudos-noded query bank balances $FIBER_ROUTER
 # cudos-noded query bank balances $CONTRACT